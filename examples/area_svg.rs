@@ -0,0 +1,29 @@
+use plotlib::page::Page;
+use plotlib::repr::{Area, Plot};
+use plotlib::style::{BoxStyle, LineStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // A mean with a band either side of it
+    let mean: Vec<(f64, f64)> = (0..=20)
+        .map(|i| f64::from(i) / 2.)
+        .map(|x| (x, 5. + 2. * (x / 2.).sin()))
+        .collect();
+    let upper = mean.iter().map(|&(x, y)| (x, y + 0.5 + 0.1 * x)).collect();
+    let lower = mean.iter().map(|&(x, y)| (x, y - 0.5 - 0.1 * x)).collect();
+
+    let band = Area::between(upper, lower)
+        .style(&BoxStyle::new().fill("steelblue").opacity(0.3))
+        .legend("spread");
+    let line = Plot::new(mean)
+        .line_style(LineStyle::new().colour("steelblue"))
+        .legend("mean".to_string());
+    let usage = Area::new(vec![(0., 1.), (3., 2.5), (6., 1.5), (10., 2.)])
+        .style(&BoxStyle::new().fill("burlywood"))
+        .edge_style(LineStyle::new().colour("saddlebrown"))
+        .legend("baseline usage");
+
+    let v = ContinuousView::new().add(usage).add(band).add(line);
+
+    Page::single(&v).save("area.svg").expect("saving svg");
+}
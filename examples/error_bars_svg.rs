@@ -0,0 +1,28 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointMarker, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Mean run times of a benchmark, with the spread of each set of runs
+    let means = vec![(1., 12.3), (2., 9.8), (4., 6.1), (8., 4.2), (16., 3.6)];
+    let spread = vec![(0.8, 1.5), (0.6, 1.1), (0.5, 0.9), (0.3, 0.8), (0.2, 1.2)];
+
+    let p = Plot::new(means)
+        .point_style(
+            PointStyle::new()
+                .marker(PointMarker::Square)
+                .colour("#DD3355"),
+        )
+        .line_style(LineStyle::new().colour("#DD3355"))
+        .y_errors_asymmetric(spread)
+        .error_style(LineStyle::new().colour("grey"))
+        .legend("mean run time".to_string());
+
+    let v = ContinuousView::new()
+        .add(p)
+        .x_label("Threads")
+        .y_label("Time (s)");
+
+    Page::single(&v).save("error_bars.svg").expect("saving svg");
+}
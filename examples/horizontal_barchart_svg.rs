@@ -0,0 +1,30 @@
+use plotlib::page::Page;
+use plotlib::repr::BarChart;
+use plotlib::style::BoxStyle;
+use plotlib::view::{CategoricalView, Orientation};
+
+fn main() {
+    let services = [
+        ("authentication-service", 52.),
+        ("billing", 31.),
+        ("search-indexer", 17.5),
+        ("notification-dispatcher", 9.),
+    ];
+
+    let v = services
+        .iter()
+        .fold(CategoricalView::new(), |v, &(name, rate)| {
+            v.add(
+                BarChart::new(rate)
+                    .label(name)
+                    .style(&BoxStyle::new().fill("steelblue")),
+            )
+        })
+        .orientation(Orientation::Horizontal)
+        .y_label("Requests per second")
+        .title("Busiest services");
+
+    Page::single(&v)
+        .save("horizontal_barchart.svg")
+        .expect("saving svg");
+}
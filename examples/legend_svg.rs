@@ -0,0 +1,47 @@
+use plotlib::legend::{Legend, LegendPosition};
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointMarker, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let f = |f: fn(f64) -> f64, colour: &str, name: &str| {
+        Plot::from_function(f, 0., 6.)
+            .line_style(LineStyle::new().colour(colour))
+            .legend(name.to_string())
+    };
+    let sin = f(f64::sin, "burlywood", "sin(x)");
+    let cos = f(f64::cos, "darkolivegreen", "cos(x)");
+    let damped = f(
+        |x| (-x / 2.).exp() * (3. * x).sin(),
+        "steelblue",
+        "a damped oscillation",
+    );
+    let points = Plot::new(vec![(1., 0.5), (2.5, -0.3), (4., 0.8)])
+        .point_style(
+            PointStyle::new()
+                .marker(PointMarker::Cross)
+                .colour("#DD3355"),
+        )
+        .legend("Measurements".to_string());
+
+    let v = ContinuousView::new()
+        .add(sin)
+        .add(cos)
+        .add(damped)
+        .add(points)
+        .x_label("x")
+        .y_label("y")
+        .legend(
+            Legend::new()
+                .position(LegendPosition::OutsideRight)
+                .title("Functions")
+                .frame(LineStyle::new().colour("grey").width(0.5))
+                .background("white"),
+        );
+
+    Page::single(&v)
+        .dimensions(700, 400)
+        .save("legend.svg")
+        .expect("saving svg");
+}
@@ -0,0 +1,31 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Request latencies which span several orders of magnitude
+    let data = vec![
+        (1., 0.8),
+        (2., 1.9),
+        (5., 7.4),
+        (10., 31.),
+        (20., 140.),
+        (50., 1100.),
+        (100., 6300.),
+    ];
+
+    let p = Plot::new(data)
+        .line_style(LineStyle::new().colour("#DD3355"))
+        .point_style(PointStyle::new().colour("#DD3355"));
+
+    // Both axes use a base-10 logarithmic scale
+    let v = ContinuousView::new()
+        .add(p)
+        .x_log(10.)
+        .y_log(10.)
+        .x_label("Concurrent requests")
+        .y_label("Latency (ms)");
+
+    Page::single(&v).save("log_scale.svg").unwrap();
+}
@@ -0,0 +1,38 @@
+use plotlib::page::Page;
+use plotlib::repr::{BarMode, BarSeries, MultiBarChart};
+use plotlib::style::BoxStyle;
+use plotlib::view::CategoricalView;
+
+fn chart(mode: BarMode) -> CategoricalView {
+    let b = MultiBarChart::new(vec!["Team A", "Team B", "Team C"])
+        .series(
+            BarSeries::new(vec![4., 2.5, 3.])
+                .style(&BoxStyle::new().fill("steelblue"))
+                .legend("CPU"),
+        )
+        .series(
+            BarSeries::new(vec![1., 3., 2.])
+                .style(&BoxStyle::new().fill("darkorange"))
+                .legend("Memory"),
+        )
+        .series(
+            BarSeries::new(vec![2., 1., 0.5])
+                .style(&BoxStyle::new().fill("seagreen"))
+                .legend("Disk"),
+        )
+        .mode(mode);
+
+    CategoricalView::new().add(b).y_label("Resources")
+}
+
+fn main() {
+    let grouped = chart(BarMode::Grouped);
+    let stacked = chart(BarMode::Stacked);
+
+    Page::single(&grouped)
+        .save("multibarchart_grouped.svg")
+        .expect("saving svg");
+    Page::single(&stacked)
+        .save("multibarchart_stacked.svg")
+        .expect("saving svg");
+}
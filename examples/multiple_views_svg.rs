@@ -0,0 +1,29 @@
+use plotlib::page::Page;
+use plotlib::repr::{Histogram, HistogramBins, Plot};
+use plotlib::style::{BoxStyle, LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let data = [0.3, 0.5, 6.4, 5.3, 3.6, 3.6, 3.5, 7.5, 4.0];
+    let h = Histogram::from_slice(&data, HistogramBins::Count(10))
+        .style(&BoxStyle::new().fill("burlywood"));
+    let histogram = ContinuousView::new().add(h);
+
+    let l = Plot::from_function(|x| x * x, 0., 5.)
+        .line_style(LineStyle::new().colour("darkolivegreen"));
+    let line = ContinuousView::new().add(l);
+
+    let s = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2), (4., 1.1)])
+        .point_style(PointStyle::new().colour("#DD3355"));
+    let scatter = ContinuousView::new().add(s);
+
+    // The histogram stretches along the whole bottom row
+    Page::empty()
+        .layout(2, 2)
+        .add_plot(&line)
+        .add_plot(&scatter)
+        .add_plot_spanning(&histogram, 1, 0, 1, 2)
+        .dimensions(800, 600)
+        .save("multiple_views.svg")
+        .expect("saving svg");
+}
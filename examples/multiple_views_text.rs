@@ -0,0 +1,20 @@
+use plotlib::page::Page;
+use plotlib::repr::{Histogram, HistogramBins, Plot};
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let data = [0.3, 0.5, 6.4, 5.3, 3.6, 3.6, 3.5, 7.5, 4.0];
+    let h = Histogram::from_slice(&data, HistogramBins::Count(10));
+    let histogram = ContinuousView::new().add(h);
+
+    let s = Plot::new(vec![(-3.0, 2.3), (-1.6, 5.3), (0.3, 0.7), (4.3, -1.4)])
+        .point_style(PointStyle::new());
+    let scatter = ContinuousView::new().add(s);
+
+    let page = Page::empty()
+        .add_plot(&histogram)
+        .add_plot(&scatter)
+        .dimensions(80, 10);
+    println!("{}", page.to_text().unwrap());
+}
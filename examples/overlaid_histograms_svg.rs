@@ -0,0 +1,28 @@
+use plotlib::legend::{Legend, LegendPosition};
+use plotlib::page::Page;
+use plotlib::repr::{Histogram, HistogramBins};
+use plotlib::style::BoxStyle;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let before = [1.2, 1.4, 1.3, 1.9, 1.6, 1.5, 1.4, 2.2, 1.5, 1.7];
+    let after = [1.0, 1.1, 0.9, 1.3, 1.2, 1.0, 1.1, 1.6, 1.2, 1.1];
+    let bins = || HistogramBins::Bounds((0..=12).map(|i| 0.8 + 0.125 * f64::from(i)).collect());
+
+    let h1 = Histogram::from_slice(&before, bins())
+        .style(&BoxStyle::new().fill("burlywood"))
+        .legend("Before");
+    let h2 = Histogram::from_slice(&after, bins())
+        .style(&BoxStyle::new().fill("steelblue"))
+        .legend("After");
+
+    let v = ContinuousView::new()
+        .add(h1)
+        .add(h2)
+        .x_label("Build time (minutes)")
+        .legend(Legend::new().position(LegendPosition::Best).title("Build"));
+
+    Page::single(&v)
+        .save("overlaid_histograms.svg")
+        .expect("saving svg");
+}
@@ -0,0 +1,33 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{PointMarker, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn main() {
+    let data = vec![
+        (-3.0, 2.3),
+        (-1.6, 5.3),
+        (0.3, 0.7),
+        (4.3, -1.4),
+        (6.4, 4.3),
+        (8.5, 3.7),
+    ];
+    let s1 = Plot::new(data).point_style(
+        PointStyle::new()
+            .marker(PointMarker::Square)
+            .colour("#DD3355"),
+    );
+
+    let v = ContinuousView::new()
+        .add(s1)
+        .x_range(-5., 10.)
+        .y_range(-2., 6.)
+        .x_label("Some varying variable")
+        .y_label("The response of something");
+
+    // Twice the default resolution
+    Page::single(&v)
+        .dpi(192.)
+        .save("scatter.png")
+        .expect("saving png");
+}
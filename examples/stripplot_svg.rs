@@ -0,0 +1,38 @@
+use plotlib::page::Page;
+use plotlib::repr::{BoxPlot, StripLayout, StripPlot};
+use plotlib::style::{BoxStyle, PointStyle};
+use plotlib::view::CategoricalView;
+
+fn main() {
+    let before = vec![
+        10.2, 11.5, 9.8, 10.9, 14.1, 10.4, 10.1, 11.2, 9.9, 10.6, 12.3, 10.8,
+    ];
+    let after = vec![8.1, 8.4, 7.9, 9.2, 8.8, 8.0, 8.3, 11.7, 8.6, 8.2, 7.7, 8.9];
+
+    let v = CategoricalView::new()
+        .add(
+            BoxPlot::from_vec(before.clone())
+                .label("Before")
+                .style(&BoxStyle::new().fill("white")),
+        )
+        .add(
+            StripPlot::from_vec(before)
+                .label("Before")
+                .style(PointStyle::new().colour("darkred"))
+                .seed(1),
+        )
+        .add(
+            BoxPlot::from_vec(after.clone())
+                .label("After")
+                .style(&BoxStyle::new().fill("white")),
+        )
+        .add(
+            StripPlot::from_vec(after)
+                .label("After")
+                .layout(StripLayout::Swarm)
+                .style(PointStyle::new().colour("steelblue")),
+        )
+        .y_label("Run time (s)");
+
+    Page::single(&v).save("stripplot.svg").expect("saving svg");
+}
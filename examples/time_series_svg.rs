@@ -0,0 +1,28 @@
+use plotlib::axis::Time;
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::LineStyle;
+use plotlib::view::ContinuousView;
+
+fn main() {
+    // Hourly request counts over three days, with times as seconds since the Unix epoch
+    let start = 1_699_920_000.;
+    let data: Vec<_> = (0..72)
+        .map(|hour| {
+            let t = start + f64::from(hour) * 3600.;
+            let load = 100. + 80. * (f64::from(hour) * std::f64::consts::PI / 12.).sin();
+            (t, load)
+        })
+        .collect();
+
+    let p = Plot::new(data).line_style(LineStyle::new().colour("#35C788"));
+
+    // The ticks land on calendar boundaries and are labelled with a strftime-style pattern
+    let v = ContinuousView::new()
+        .add(p)
+        .x_transform(Time::new().format("%a %H:%M"))
+        .x_label("Time (UTC)")
+        .y_label("Requests per hour");
+
+    Page::single(&v).save("time_series.svg").unwrap();
+}
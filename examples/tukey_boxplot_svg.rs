@@ -0,0 +1,35 @@
+use plotlib::page::Page;
+use plotlib::repr::{BoxPlot, WhiskerRule};
+use plotlib::style::{BoxStyle, PointMarker, PointStyle};
+use plotlib::view::CategoricalView;
+
+fn main() {
+    let latencies = [
+        3.0, 12.0, 14.0, 15.0, 15.0, 16.0, 18.0, 19.0, 22.0, 24.0, 26.0, 30.0, 48.0, 95.0,
+    ];
+
+    let min_max = BoxPlot::from_vec(latencies.to_vec()).label("Min/max");
+    let tukey = BoxPlot::from_vec(latencies.to_vec())
+        .label("Tukey")
+        .whiskers(WhiskerRule::Tukey)
+        .style(&BoxStyle::new().fill("lightsteelblue"))
+        .outlier_style(PointStyle::new().marker(PointMarker::Cross).colour("red"))
+        .mean_style(PointStyle::new().marker(PointMarker::Diamond))
+        .notched(true);
+    let percentiles = BoxPlot::from_vec(latencies.to_vec())
+        .label("5th to 95th")
+        .whiskers(WhiskerRule::Percentiles(5., 95.))
+        .style(&BoxStyle::new().fill("wheat"));
+
+    let v = CategoricalView::new()
+        .add(min_max)
+        .add(tukey)
+        .add(percentiles)
+        .x_label("Whiskers")
+        .y_label("Latency (ms)");
+
+    Page::single(&v)
+        .dimensions(400, 300)
+        .save("tukey_boxplot.svg")
+        .expect("saving svg");
+}
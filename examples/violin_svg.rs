@@ -0,0 +1,30 @@
+use plotlib::page::Page;
+use plotlib::repr::{Violin, ViolinInner};
+use plotlib::style::BoxStyle;
+use plotlib::view::CategoricalView;
+
+fn main() {
+    // Cache lookups are either fast hits or slow misses
+    let hits = vec![0.8, 0.9, 1.0, 1.0, 1.1, 1.2, 1.3, 1.5];
+    let misses = vec![3.2, 3.6, 3.9, 4.0, 4.2, 4.4, 4.9, 5.6];
+    let all: Vec<f64> = hits.iter().chain(&misses).cloned().collect();
+
+    let combined = Violin::from_vec(all)
+        .label("All lookups")
+        .style(&BoxStyle::new().fill("lightgrey"));
+    let split = Violin::from_vec(hits)
+        .split_with(misses)
+        .label("By outcome")
+        .inner(ViolinInner::Quartiles)
+        .legend("Hit")
+        .style(&BoxStyle::new().fill("lightsteelblue"))
+        .split_legend("Miss")
+        .split_style(&BoxStyle::new().fill("wheat"));
+
+    let v = CategoricalView::new()
+        .add(combined)
+        .add(split)
+        .y_label("Time (ms)");
+
+    Page::single(&v).save("violin.svg").expect("saving svg");
+}
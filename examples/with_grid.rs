@@ -7,6 +7,7 @@ use plotlib::view::{CategoricalView, ContinuousView, View};
 fn main() {
     render_line_chart("line_with_grid.svg");
     render_barchart("barchart_with_grid.svg");
+    render_tick_grid("line_with_tick_grid.svg");
 }
 
 fn render_line_chart<S>(filename: S)
@@ -36,3 +37,23 @@ where
         .save(filename.as_ref())
         .expect("saving svg");
 }
+
+fn render_tick_grid<S>(filename: S)
+where
+    S: AsRef<str>,
+{
+    let l1 = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2), (4., 1.1)])
+        .line_style(LineStyle::new().colour("burlywood"));
+    let mut v = ContinuousView::new()
+        .add(l1)
+        .x_minor_ticks(4)
+        .y_minor_ticks(2);
+    v.add_grid(
+        Grid::at_ticks()
+            .include_minor(true)
+            .minor_style(LineStyle::new().colour("grey").dash(&[2., 2.]).opacity(0.5)),
+    );
+    Page::single(&v)
+        .save(filename.as_ref())
+        .expect("saving svg");
+}
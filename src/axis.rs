@@ -2,8 +2,18 @@
 
 A module for managing axes
 
+Representations are given the axes of the view they are in when they are drawn,
+and use them to work out where on the face of the view their data should be placed.
+
 */
 
+use std::fmt;
+use std::rc::Rc;
+
+use crate::time;
+use crate::utils;
+
+/// The extent of a continuous axis, from `lower` to `upper`
 #[derive(Debug, Clone)]
 pub struct Range {
     pub lower: f64,
@@ -20,19 +30,532 @@ impl Range {
     }
 }
 
+/**
+How values are spaced along a continuous axis.
+
+A transform maps each value into a space in which the axis is evenly spaced,
+so that e.g. a logarithmic axis maps each value to its logarithm.
+Implement this to provide a custom scale,
+or use [`FunctionTransform`](struct.FunctionTransform.html) to build one from a pair of closures.
+*/
+pub trait AxisTransform: fmt::Debug {
+    /// Map a value into the space in which the axis is evenly spaced
+    fn apply(&self, value: f64) -> f64;
+
+    /// The inverse of `apply`, mapping from the evenly spaced space back to a value
+    fn invert(&self, value: f64) -> f64;
+
+    /// Check that the parameters of the transform make sense, giving the reason if they do not
+    fn validate(&self) -> std::result::Result<(), String> {
+        Ok(())
+    }
+
+    /// Whether a value can be shown at all on an axis with this transform
+    fn is_valid_value(&self, value: f64) -> bool {
+        value.is_finite() && self.apply(value).is_finite()
+    }
+
+    /// Choose where to put the ticks on an axis covering `lower` to `upper`.
+    ///
+    /// By default the ticks are evenly spaced in the transformed space
+    /// and then rounded to a couple of significant figures.
+    fn ticks(&self, lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+        let mut ticks: Vec<f64> = calculate_ticks(self.apply(lower), self.apply(upper), max_ticks)
+            .into_iter()
+            .map(|t| round_to_significant_figures(self.invert(t), 2))
+            .filter(|&t| t >= lower && t <= upper)
+            .collect();
+        ticks.dedup();
+        ticks
+    }
+
+    /// The text to label a single tick with
+    fn tick_label(&self, tick: f64) -> String {
+        self.tick_labels(&[tick]).remove(0)
+    }
+
+    /// The text to label each of the ticks on an axis with.
+    ///
+    /// By default this uses [`TickFormat::Auto`](enum.TickFormat.html),
+    /// showing just enough decimal places to tell the ticks apart.
+    fn tick_labels(&self, ticks: &[f64]) -> Vec<String> {
+        TickFormat::Auto.format_ticks(ticks)
+    }
+
+    /// Widen a range of data which covers only a single value so that the axis can be drawn
+    fn pad_range(&self, min: f64, max: f64) -> (f64, f64) {
+        if (min - max).abs() >= f64::EPSILON {
+            (min, max)
+        } else if self.is_valid_value(0.) {
+            utils::pad_range_to_zero(min, max)
+        } else {
+            (
+                self.invert(self.apply(min) - 1.),
+                self.invert(self.apply(max) + 1.),
+            )
+        }
+    }
+}
+
+/// Equal differences in value are equal distances along the axis
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Linear;
+
+impl AxisTransform for Linear {
+    fn apply(&self, value: f64) -> f64 {
+        value
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        value
+    }
+
+    fn ticks(&self, lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+        calculate_ticks(lower, upper, max_ticks)
+    }
+}
+
+/// Equal ratios in value are equal distances along the axis. Only positive values can be shown.
+#[derive(Debug, Clone, Copy)]
+pub struct Log {
+    base: f64,
+}
+
+impl Log {
+    pub fn new(base: f64) -> Self {
+        Log { base }
+    }
+}
+
+/// Check that a logarithm can be taken with a base
+fn validate_log_base(base: f64) -> std::result::Result<(), String> {
+    if base.is_finite() && base > 0. && (base - 1.).abs() > f64::EPSILON {
+        Ok(())
+    } else {
+        Err(format!(
+            "the base of a logarithm must be positive and not 1, but is {}",
+            base
+        ))
+    }
+}
+
+impl AxisTransform for Log {
+    fn validate(&self) -> std::result::Result<(), String> {
+        validate_log_base(self.base)
+    }
+
+    fn apply(&self, value: f64) -> f64 {
+        value.log(self.base)
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        self.base.powf(value)
+    }
+
+    fn ticks(&self, lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+        calculate_log_ticks(lower, upper, self.base, max_ticks)
+    }
+
+    fn tick_labels(&self, ticks: &[f64]) -> Vec<String> {
+        ticks
+            .iter()
+            .map(|&tick| log_tick_label(tick, self.base))
+            .collect()
+    }
+}
+
+/**
+Linear between `-threshold` and `threshold`, and logarithmic beyond that in both directions.
+
+This can show data which crosses zero but also spans many orders of magnitude.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct SymLog {
+    base: f64,
+    threshold: f64,
+}
+
+impl SymLog {
+    pub fn new(base: f64, threshold: f64) -> Self {
+        SymLog { base, threshold }
+    }
+}
+
+impl AxisTransform for SymLog {
+    fn validate(&self) -> std::result::Result<(), String> {
+        validate_log_base(self.base)?;
+        if self.threshold.is_finite() && self.threshold > 0. {
+            Ok(())
+        } else {
+            Err(format!(
+                "the threshold of a symmetric logarithm must be positive, but is {}",
+                self.threshold
+            ))
+        }
+    }
+
+    fn apply(&self, value: f64) -> f64 {
+        let magnitude = value.abs() / self.threshold;
+        if magnitude <= 1. {
+            value / self.threshold
+        } else {
+            value.signum() * (1. + magnitude.log(self.base))
+        }
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        if value.abs() <= 1. {
+            value * self.threshold
+        } else {
+            value.signum() * self.threshold * self.base.powf(value.abs() - 1.)
+        }
+    }
+
+    /// Ticks go at zero and at the threshold multiplied by each power of the base
+    fn ticks(&self, lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+        let largest = lower.abs().max(upper.abs());
+        let powers: Vec<f64> = (0..)
+            .map(|power| self.threshold * self.base.powi(power))
+            .take_while(|&t| t <= largest * (1. + 1e-9))
+            .collect();
+        let mut ticks: Vec<f64> = powers.iter().rev().map(|&t| -t).collect();
+        ticks.push(0.);
+        ticks.extend(powers);
+        let ticks: Vec<f64> = ticks
+            .into_iter()
+            .filter(|&t| t >= lower && t <= upper)
+            .collect();
+        if ticks.len() < 2 {
+            return calculate_ticks(lower, upper, max_ticks);
+        }
+
+        // Thin the ticks out symmetrically about zero
+        let step = ticks.len().div_ceil(max_ticks.max(1));
+        let zero = ticks.iter().position(|&t| t == 0.).unwrap_or(0);
+        ticks
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, _)| (i as i64 - zero as i64) % step as i64 == 0)
+            .map(|(_, t)| t)
+            .collect()
+    }
+
+    fn tick_labels(&self, ticks: &[f64]) -> Vec<String> {
+        ticks
+            .iter()
+            .map(|&tick| {
+                if tick < 0. {
+                    format!("-{}", log_tick_label(-tick, self.base))
+                } else if tick == 0. {
+                    "0".into()
+                } else {
+                    log_tick_label(tick, self.base)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Values are spaced by their square root. Only non-negative values can be shown.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sqrt;
+
+impl AxisTransform for Sqrt {
+    fn apply(&self, value: f64) -> f64 {
+        value.sqrt()
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        value * value
+    }
+}
+
+/**
+Values are spaced by their log-odds, `ln(p / (1 - p))`.
+
+This stretches out the ends of the range between 0 and 1 so is suited to probabilities and rates.
+Only values strictly between 0 and 1 can be shown.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Logit;
+
+impl AxisTransform for Logit {
+    fn apply(&self, value: f64) -> f64 {
+        (value / (1. - value)).ln()
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        1. / (1. + (-value).exp())
+    }
+
+    /// Ticks go at one half and at each power of ten away from 0 and 1
+    fn ticks(&self, lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+        let mut ticks: Vec<f64> = (1..=9)
+            .rev()
+            .map(|power| fix_precision(10f64.powi(-power)))
+            .chain(std::iter::once(0.5))
+            .chain((1..=9).map(|power| fix_precision(1. - 10f64.powi(-power))))
+            .filter(|&t| t >= lower && t <= upper)
+            .collect();
+        if ticks.len() < 2 {
+            return calculate_ticks(lower, upper, max_ticks);
+        }
+
+        let step = ticks.len().div_ceil(max_ticks.max(1));
+        if step > 1 {
+            let middle = ticks.len() / 2;
+            ticks = ticks
+                .into_iter()
+                .enumerate()
+                .filter(|&(i, _)| (i as i64 - middle as i64) % step as i64 == 0)
+                .map(|(_, t)| t)
+                .collect();
+        }
+        ticks
+    }
+
+    fn tick_labels(&self, ticks: &[f64]) -> Vec<String> {
+        // The ticks get closer to 0 and 1 by a factor of ten each time,
+        // so each is shown with as many decimal places as it needs
+        ticks.iter().map(|tick| tick.to_string()).collect()
+    }
+}
+
+/**
+A linear axis of times, given as seconds since the Unix epoch in UTC.
+
+Ticks are placed on calendar boundaries such as the start of an hour, a day or a month.
+They are labelled with a strftime-style pattern which,
+unless one is set with `format`, shows just enough to tell the ticks apart.
+Times more than about thirty million years either side of the epoch cannot be shown.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Time {
+    format: Option<String>,
+}
+
+impl Time {
+    pub fn new() -> Self {
+        Time { format: None }
+    }
+
+    /**
+    Set the pattern to label the ticks with.
+
+    The supported specifiers are
+    `%Y`, `%y`, `%m`, `%d`, `%e`, `%j`, `%H`, `%I`, `%p`, `%M`, `%S`, `%f`,
+    `%b`, `%B`, `%a`, `%A` and `%%`.
+    */
+    pub fn format<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.format = Some(pattern.into());
+        self
+    }
+}
+
+impl AxisTransform for Time {
+    fn apply(&self, value: f64) -> f64 {
+        value
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        value
+    }
+
+    /// Times too far from the epoch to put on the calendar cannot be shown
+    fn is_valid_value(&self, value: f64) -> bool {
+        value.abs() <= time::MAX_TIMESTAMP
+    }
+
+    fn ticks(&self, lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+        time::calculate_time_ticks(lower, upper, max_ticks)
+    }
+
+    /// A single time is shown within its day, rather than padding the axis back to the epoch
+    fn pad_range(&self, min: f64, max: f64) -> (f64, f64) {
+        if (min - max).abs() >= f64::EPSILON {
+            (min, max)
+        } else {
+            time::day_around(min)
+        }
+    }
+
+    fn tick_labels(&self, ticks: &[f64]) -> Vec<String> {
+        let pattern = match self.format {
+            Some(ref pattern) => pattern.as_str(),
+            None => time::default_format(ticks),
+        };
+        ticks
+            .iter()
+            .map(|&tick| time::DateTime::from_timestamp(tick).format(pattern))
+            .collect()
+    }
+}
+
+/// A transform built from a pair of functions, which must be the inverse of one another
+#[derive(Clone)]
+pub struct FunctionTransform {
+    forward: Rc<dyn Fn(f64) -> f64>,
+    inverse: Rc<dyn Fn(f64) -> f64>,
+}
+
+impl FunctionTransform {
+    pub fn new<F, I>(forward: F, inverse: I) -> Self
+    where
+        F: Fn(f64) -> f64 + 'static,
+        I: Fn(f64) -> f64 + 'static,
+    {
+        FunctionTransform {
+            forward: Rc::new(forward),
+            inverse: Rc::new(inverse),
+        }
+    }
+}
+
+impl fmt::Debug for FunctionTransform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FunctionTransform").finish()
+    }
+}
+
+impl AxisTransform for FunctionTransform {
+    fn apply(&self, value: f64) -> f64 {
+        (self.forward)(value)
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        (self.inverse)(value)
+    }
+}
+
+/**
+How the ticks on a continuous axis are labelled.
+
+The formats which take a `usize` show that many decimal places.
+*/
+#[derive(Clone, Default)]
+pub enum TickFormat {
+    /// Show just enough decimal places to tell the ticks apart,
+    /// switching to scientific notation for very large or very small values
+    #[default]
+    Auto,
+    /// A fixed number of decimal places, e.g. `1.50`
+    Fixed(usize),
+    /// Scientific notation, e.g. `1.50e6`
+    Scientific(usize),
+    /// SI prefixes, e.g. `1.5k` or `20M`
+    SI,
+    /// The values as percentages, so that `0.25` is shown as `25%`
+    Percent(usize),
+    /// Like `Auto`, but with commas separating the thousands, e.g. `1,500,000`
+    Thousands,
+    /// Label each tick with a user-supplied function
+    Custom(Rc<dyn Fn(f64) -> String>),
+}
+
+impl TickFormat {
+    /// Label the ticks with a user-supplied function
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(f64) -> String + 'static,
+    {
+        TickFormat::Custom(Rc::new(f))
+    }
+
+    /// The labels for each of `ticks`
+    pub fn format_ticks(&self, ticks: &[f64]) -> Vec<String> {
+        match *self {
+            TickFormat::Auto => {
+                let largest = ticks.iter().fold(0., |m: f64, t| m.max(t.abs()));
+                let smallest = ticks
+                    .iter()
+                    .filter(|&&t| t != 0.)
+                    .fold(f64::INFINITY, |m: f64, t| m.min(t.abs()));
+                if largest >= 1e6 || smallest < 1e-4 {
+                    let decimals = fewest_decimals(ticks, format_scientific);
+                    ticks
+                        .iter()
+                        .map(|&t| format_scientific(t, decimals))
+                        .collect()
+                } else {
+                    let decimals = fewest_decimals(ticks, format_fixed);
+                    ticks.iter().map(|&t| format_fixed(t, decimals)).collect()
+                }
+            }
+            TickFormat::Fixed(decimals) => {
+                ticks.iter().map(|&t| format_fixed(t, decimals)).collect()
+            }
+            TickFormat::Scientific(decimals) => ticks
+                .iter()
+                .map(|&t| format_scientific(t, decimals))
+                .collect(),
+            TickFormat::SI => ticks.iter().map(|&t| format_si(t)).collect(),
+            TickFormat::Percent(decimals) => ticks
+                .iter()
+                .map(|&t| format!("{}%", format_fixed(t * 100., decimals)))
+                .collect(),
+            TickFormat::Thousands => {
+                let decimals = fewest_decimals(ticks, format_fixed);
+                ticks
+                    .iter()
+                    .map(|&t| separate_thousands(&format_fixed(t, decimals)))
+                    .collect()
+            }
+            TickFormat::Custom(ref f) => ticks.iter().map(|&t| f(t)).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for TickFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TickFormat::Auto => write!(f, "Auto"),
+            TickFormat::Fixed(d) => write!(f, "Fixed({})", d),
+            TickFormat::Scientific(d) => write!(f, "Scientific({})", d),
+            TickFormat::SI => write!(f, "SI"),
+            TickFormat::Percent(d) => write!(f, "Percent({})", d),
+            TickFormat::Thousands => write!(f, "Thousands"),
+            TickFormat::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ContinuousAxis {
     range: Range,
+    transform: Rc<dyn AxisTransform>,
     ticks: Vec<f64>,
+    tick_labels: Vec<String>,
+    tick_format: Option<TickFormat>,
+    minor_ticks: Vec<f64>,
+    minor_subdivisions: usize,
     label: String,
 }
 
 impl ContinuousAxis {
     /// Constructs a new ContinuousAxis
     pub fn new(lower: f64, upper: f64, max_ticks: usize) -> ContinuousAxis {
+        ContinuousAxis::with_transform(lower, upper, max_ticks, Rc::new(Linear))
+    }
+
+    /// Constructs a new ContinuousAxis with values spaced according to `transform`
+    pub fn with_transform(
+        lower: f64,
+        upper: f64,
+        max_ticks: usize,
+        transform: Rc<dyn AxisTransform>,
+    ) -> ContinuousAxis {
+        let ticks = transform.ticks(lower, upper, max_ticks);
         ContinuousAxis {
             range: Range::new(lower, upper),
-            ticks: calculate_ticks(lower, upper, max_ticks),
+            tick_labels: transform.tick_labels(&ticks),
+            ticks,
+            transform,
+            tick_format: None,
+            minor_ticks: vec![],
+            minor_subdivisions: 0,
             label: "".into(),
         }
     }
@@ -57,10 +580,117 @@ impl ContinuousAxis {
         self.label.as_ref()
     }
 
+    /// Label the ticks using `format` rather than the default labels of the transform
+    pub fn tick_format(mut self, format: TickFormat) -> Self {
+        self.tick_labels = format.format_ticks(&self.ticks);
+        self.tick_format = Some(format);
+        self
+    }
+
+    /**
+    Put the ticks at exactly the given values, rather than choosing them automatically.
+
+    Any values outside the range of the axis are ignored.
+    Any minor ticks are placed again between the new ticks.
+    */
+    pub fn tick_positions(mut self, ticks: &[f64]) -> Self {
+        self.ticks = ticks
+            .iter()
+            .cloned()
+            .filter(|&t| t >= self.min() && t <= self.max())
+            .collect();
+        self.tick_labels = match self.tick_format {
+            Some(ref format) => format.format_ticks(&self.ticks),
+            None => self.transform.tick_labels(&self.ticks),
+        };
+        self.place_minor_ticks();
+        self
+    }
+
+    /// Put the ticks at exactly the given values, each labelled with the accompanying text
+    pub fn labelled_ticks<S>(mut self, ticks: &[(f64, S)]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let (positions, labels): (Vec<f64>, Vec<String>) = ticks
+            .iter()
+            .filter(|(t, _)| *t >= self.min() && *t <= self.max())
+            .map(|(t, l)| (*t, l.as_ref().to_string()))
+            .unzip();
+        self = self.tick_positions(&positions);
+        self.tick_labels = labels;
+        self
+    }
+
+    /**
+    Divide the space between each pair of ticks into `subdivisions` equal parts
+    with unlabelled minor ticks.
+
+    The parts are of equal size in value rather than in distance along the axis,
+    so that on a base-10 logarithmic axis 9 subdivisions put minor ticks at 2, 3, 4, ... 9.
+    */
+    pub fn minor_ticks(mut self, subdivisions: usize) -> Self {
+        self.minor_subdivisions = subdivisions;
+        self.place_minor_ticks();
+        self
+    }
+
+    fn place_minor_ticks(&mut self) {
+        self.minor_ticks = calculate_minor_ticks(
+            self.transform.as_ref(),
+            &self.ticks,
+            self.min(),
+            self.max(),
+            self.minor_subdivisions,
+        );
+    }
+
     /// Get the positions of the ticks on the axis
     pub fn ticks(&self) -> &Vec<f64> {
         &self.ticks
     }
+
+    /// Get the positions of the minor ticks on the axis
+    pub fn get_minor_ticks(&self) -> &Vec<f64> {
+        &self.minor_ticks
+    }
+
+    pub fn get_transform(&self) -> &dyn AxisTransform {
+        self.transform.as_ref()
+    }
+
+    /// The text to label a tick with
+    pub fn tick_label(&self, tick: f64) -> String {
+        match self.ticks.iter().position(|&t| t == tick) {
+            Some(index) => self.tick_labels[index].clone(),
+            None => match self.tick_format {
+                Some(ref format) => format.format_ticks(&[tick]).remove(0),
+                None => self.transform.tick_label(tick),
+            },
+        }
+    }
+
+    /// The value that bars and other filled areas should extend from.
+    ///
+    /// This is zero, unless zero cannot be shown on the axis
+    /// in which case it is the lower end of the axis.
+    pub fn baseline(&self) -> f64 {
+        if self.transform.is_valid_value(0.) {
+            0.
+        } else {
+            self.min()
+        }
+    }
+
+    /// Given a value along the axis, how far along a face of length `face_size` it lies.
+    ///
+    /// The minimum of the axis is at 0 and the maximum is at `face_size`.
+    /// Values outside the range of the axis will lie off the face.
+    pub fn value_to_face_offset(&self, value: f64, face_size: f64) -> f64 {
+        let min = self.transform.apply(self.min());
+        let range = self.transform.apply(self.max()) - min;
+        (face_size * (self.transform.apply(value) - min)) / range
+    }
 }
 
 #[derive(Debug)]
@@ -94,6 +724,20 @@ impl CategoricalAxis {
     pub fn ticks(&self) -> &Vec<String> {
         &self.ticks
     }
+
+    /// The space along a face of length `face_size` given to each category
+    pub fn category_width(&self, face_size: f64) -> f64 {
+        face_size / self.ticks.len() as f64
+    }
+
+    /// Given a category, how far along a face of length `face_size` its centre lies.
+    ///
+    /// Returns `None` if the category is not on the axis.
+    pub fn category_to_face_offset(&self, category: &str, face_size: f64) -> Option<f64> {
+        let index = self.ticks.iter().position(|t| t == category)?;
+        let width = self.category_width(face_size);
+        Some((index as f64 * width) + (0.5 * width))
+    }
 }
 
 /// The base units for the step sizes
@@ -138,6 +782,21 @@ impl Iterator for TickSteps {
     }
 }
 
+/// Round away any floating-point errors
+fn fix_precision(x: f64) -> f64 {
+    const PRECISION: f64 = 1e12;
+    (x * PRECISION).round() / PRECISION
+}
+
+/// Round a value to `figures` significant figures
+fn round_to_significant_figures(x: f64, figures: i32) -> f64 {
+    if x == 0. || !x.is_finite() {
+        return x;
+    }
+    let magnitude = 10f64.powi(figures - 1 - x.abs().log10().floor() as i32);
+    (x * magnitude).round() / magnitude
+}
+
 fn generate_ticks(min: f64, max: f64, step_size: f64) -> Vec<f64> {
     // "fix" just makes sure there are no floating-point errors
     fn fix(x: f64) -> f64 {
@@ -215,10 +874,284 @@ fn calculate_ticks(min: f64, max: f64, max_ticks: usize) -> Vec<f64> {
     generate_ticks(min, max, tick_step)
 }
 
+/// Given an axis range, place ticks on the powers of `base` within it.
+///
+/// If the range does not cover at least two powers, there is not enough to show the scale
+/// so ticks are placed linearly instead.
+fn calculate_log_ticks(min: f64, max: f64, base: f64, max_ticks: usize) -> Vec<f64> {
+    // Allow a little leeway so that e.g. 1000 is counted as exactly the third power of 10
+    const TOLERANCE: f64 = 1e-9;
+    let lowest = (min.log(base) - TOLERANCE).ceil() as i32;
+    let highest = (max.log(base) + TOLERANCE).floor() as i32;
+    if highest <= lowest {
+        return calculate_ticks(min, max, max_ticks);
+    }
+
+    let num_powers = (highest - lowest + 1) as usize;
+    let step = num_powers.div_ceil(max_ticks.max(1));
+    (lowest..=highest)
+        .step_by(step)
+        .map(|power| base.powi(power))
+        .collect()
+}
+
+/// The fewest decimal places with which `format` shows each of the ticks close to its true value
+fn fewest_decimals<F>(ticks: &[f64], format: F) -> usize
+where
+    F: Fn(f64, usize) -> String,
+{
+    let mut sorted: Vec<f64> = ticks.iter().cloned().filter(|t| t.is_finite()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let spacing = sorted
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|&d| d > 0.)
+        .fold(f64::INFINITY, f64::min);
+    let tolerance = if spacing.is_finite() {
+        spacing * 1e-3
+    } else {
+        sorted.iter().fold(0., |m: f64, t| m.max(t.abs())) * 1e-9
+    };
+    (0..=15)
+        .find(|&decimals| {
+            sorted.iter().all(|&t| {
+                let shown: f64 = format(t, decimals).parse().unwrap_or(f64::NAN);
+                (shown - t).abs() <= tolerance
+            })
+        })
+        .unwrap_or(15)
+}
+
+/// Remove the sign from labels such as `-0.0`
+fn strip_negative_zero(label: String) -> String {
+    match label.strip_prefix('-') {
+        Some(rest) if rest.chars().all(|c| c == '0' || c == '.') => rest.to_string(),
+        _ => label,
+    }
+}
+
+fn format_fixed(value: f64, decimals: usize) -> String {
+    strip_negative_zero(format!("{:.*}", decimals, value))
+}
+
+fn format_scientific(value: f64, decimals: usize) -> String {
+    if value == 0. {
+        return format_fixed(0., decimals);
+    }
+    format!("{:.*e}", decimals, value)
+}
+
+fn format_si(value: f64) -> String {
+    const PREFIXES: [&str; 9] = ["p", "n", "µ", "m", "", "k", "M", "G", "T"];
+    if value == 0. || !value.is_finite() {
+        return format_fixed(value, 0);
+    }
+    let group = ((value.abs().log10() / 3.).floor() as i32).clamp(-4, 4);
+    let mantissa = format_fixed(value / 1000f64.powi(group), 3);
+    let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}", mantissa, PREFIXES[(group + 4) as usize])
+}
+
+/// Put commas between each group of three digits before the decimal point
+fn separate_thousands(label: &str) -> String {
+    let (sign, unsigned) = match label.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", label),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(index) => unsigned.split_at(index),
+        None => (unsigned, ""),
+    };
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{}{}", sign, grouped, fraction)
+}
+
+/// Place minor ticks between each pair of major ticks,
+/// and before the first and after the last where they lie within `lower` and `upper`
+fn calculate_minor_ticks(
+    transform: &dyn AxisTransform,
+    major: &[f64],
+    lower: f64,
+    upper: f64,
+    subdivisions: usize,
+) -> Vec<f64> {
+    if major.len() < 2 || subdivisions < 2 {
+        return vec![];
+    }
+    // Extend the ticks by one step at each end, in the transformed space
+    let first = transform.apply(major[0]);
+    let second = transform.apply(major[1]);
+    let last = transform.apply(major[major.len() - 1]);
+    let second_last = transform.apply(major[major.len() - 2]);
+    let mut bounds = vec![transform.invert(2. * first - second)];
+    bounds.extend_from_slice(major);
+    bounds.push(transform.invert(2. * last - second_last));
+
+    bounds
+        .windows(2)
+        .filter(|w| w[0].is_finite() && w[1].is_finite() && w[0] < w[1])
+        .flat_map(|w| {
+            let step = (w[1] - w[0]) / subdivisions as f64;
+            (1..subdivisions).map(move |i| fix_precision(w[0] + step * i as f64))
+        })
+        .filter(|&t| t >= lower && t <= upper && !major.contains(&t))
+        .collect()
+}
+
+/// Label a tick on a logarithmic axis.
+///
+/// Powers of the base which are short to write out, such as 1000 or 0.01, are written in full.
+/// Other powers are written as `base^power`.
+fn log_tick_label(tick: f64, base: f64) -> String {
+    let power = tick.log(base).round();
+    let plain = tick.to_string();
+    if (base.powf(power) - tick).abs() > 1e-9 * tick || plain.len() <= 4 {
+        return plain;
+    }
+    if (base - std::f64::consts::E).abs() < f64::EPSILON {
+        format!("e^{}", power)
+    } else {
+        format!("{}^{}", base, power)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_value_to_face_offset() {
+        let axis = ContinuousAxis::new(-2., 5., 6);
+        assert_eq!(axis.value_to_face_offset(-2.0, 14.0), 0.0);
+        assert_eq!(axis.value_to_face_offset(5.0, 14.0), 14.0);
+        assert_eq!(axis.value_to_face_offset(0.0, 14.0), 4.0);
+        assert_eq!(axis.value_to_face_offset(-4.0, 14.0), -4.0);
+        assert_eq!(axis.value_to_face_offset(7.0, 14.0), 18.0);
+    }
+
+    #[test]
+    fn test_log_value_to_face_offset() {
+        let axis = ContinuousAxis::with_transform(1., 1000., 6, Rc::new(Log::new(10.)));
+        assert_eq!(axis.value_to_face_offset(1., 30.0), 0.0);
+        assert_eq!(axis.value_to_face_offset(1000., 30.0), 30.0);
+        assert!((axis.value_to_face_offset(10., 30.0) - 10.0).abs() < 1e-9);
+        assert!((axis.value_to_face_offset(100., 30.0) - 20.0).abs() < 1e-9);
+        assert_eq!(axis.baseline(), 1.);
+    }
+
+    #[test]
+    fn test_transforms_invert() {
+        let transforms: Vec<(Box<dyn AxisTransform>, Vec<f64>)> = vec![
+            (Box::new(Linear), vec![-3., 0., 2.5]),
+            (Box::new(Log::new(2.)), vec![0.25, 1., 10.]),
+            (
+                Box::new(SymLog::new(10., 1.)),
+                vec![-500., -0.5, 0., 0.3, 1., 42.],
+            ),
+            (Box::new(Sqrt), vec![0., 2., 9.]),
+            (Box::new(Logit), vec![0.001, 0.5, 0.75]),
+            (
+                Box::new(FunctionTransform::new(|x| x.powi(3), f64::cbrt)),
+                vec![-2., 0., 3.],
+            ),
+        ];
+        for (transform, values) in transforms {
+            for value in values {
+                let round_trip = transform.invert(transform.apply(value));
+                assert!(
+                    (round_trip - value).abs() < 1e-9,
+                    "{:?} did not invert {}",
+                    transform,
+                    value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_domains() {
+        assert!(Linear.is_valid_value(-1.));
+        assert!(!Log::new(10.).is_valid_value(0.));
+        assert!(SymLog::new(10., 1.).is_valid_value(-1e6));
+        assert!(Sqrt.is_valid_value(0.));
+        assert!(!Sqrt.is_valid_value(-1.));
+        assert!(!Logit.is_valid_value(0.));
+        assert!(!Logit.is_valid_value(1.));
+        assert!(Logit.is_valid_value(0.2));
+    }
+
+    #[test]
+    fn test_transform_ticks() {
+        assert_eq!(
+            SymLog::new(10., 1.).ticks(-1000., 1000., 9),
+            [-1000., -100., -10., -1., 0., 1., 10., 100., 1000.]
+        );
+        assert_eq!(
+            SymLog::new(10., 1.).ticks(-1000., 1000., 5),
+            [-1000., -10., 0., 10., 1000.]
+        );
+        assert_eq!(
+            Logit.ticks(0.001, 0.999, 7),
+            [0.001, 0.01, 0.1, 0.5, 0.9, 0.99, 0.999]
+        );
+        assert_eq!(Sqrt.ticks(0., 100., 6), [0., 4., 16., 36., 64., 100.]);
+        assert_eq!(Log::new(10.).ticks(1., 100., 6), [1., 10., 100.]);
+    }
+
+    #[test]
+    fn test_transform_pad_range() {
+        assert_eq!(Linear.pad_range(2., 2.), (0., 2.));
+        assert_eq!(Linear.pad_range(1., 2.), (1., 2.));
+        assert_eq!(Log::new(10.).pad_range(10., 10.), (1., 100.));
+    }
+
+    #[test]
+    fn test_calculate_log_ticks() {
+        assert_eq!(
+            calculate_log_ticks(1., 1000., 10., 6),
+            [1., 10., 100., 1000.]
+        );
+        assert_eq!(calculate_log_ticks(0.5, 20., 10., 6), [1., 10.]);
+        assert_eq!(
+            calculate_log_ticks(1., 1e10, 10., 6),
+            [1., 1e2, 1e4, 1e6, 1e8, 1e10]
+        );
+        assert_eq!(calculate_log_ticks(1., 8., 2., 6), [1., 2., 4., 8.]);
+        // Not enough powers in the range
+        assert_eq!(calculate_log_ticks(2., 8., 10., 6), [2., 4., 6., 8.]);
+    }
+
+    #[test]
+    fn test_log_tick_label() {
+        assert_eq!(log_tick_label(1., 10.), "1");
+        assert_eq!(log_tick_label(1000., 10.), "1000");
+        assert_eq!(log_tick_label(0.01, 10.), "0.01");
+        assert_eq!(log_tick_label(1e6, 10.), "10^6");
+        assert_eq!(log_tick_label(1e-5, 10.), "10^-5");
+        assert_eq!(log_tick_label(8., 2.), "8");
+        assert_eq!(log_tick_label(1048576., 2.), "2^20");
+        assert_eq!(
+            log_tick_label(std::f64::consts::E.powi(3), std::f64::consts::E),
+            "e^3"
+        );
+        assert_eq!(log_tick_label(6., 10.), "6");
+    }
+
+    #[test]
+    fn test_category_to_face_offset() {
+        let axis = CategoricalAxis::new(&["a".to_string(), "b".to_string()]);
+        assert_eq!(axis.category_width(10.0), 5.0);
+        assert_eq!(axis.category_to_face_offset("a", 10.0), Some(2.5));
+        assert_eq!(axis.category_to_face_offset("b", 10.0), Some(7.5));
+        assert_eq!(axis.category_to_face_offset("c", 10.0), None);
+    }
+
     #[test]
     fn test_tick_step_generator() {
         let t = TickSteps::start_at(1.0);
@@ -385,4 +1318,88 @@ mod tests {
         assert_eq!(calculate_ticks(0.0, 1.0, 6), [0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
         assert_eq!(calculate_ticks(0.0, 0.3, 4), [0.0, 0.1, 0.2, 0.3]);
     }
+
+    #[test]
+    fn test_auto_tick_format() {
+        let auto = TickFormat::Auto;
+        assert_eq!(auto.format_ticks(&[0., 1., 2.]), ["0", "1", "2"]);
+        assert_eq!(
+            auto.format_ticks(&[0.1, 0.2, 0.1 + 0.2]),
+            ["0.1", "0.2", "0.3"]
+        );
+        assert_eq!(
+            auto.format_ticks(&[1., 1.25, 1.5]),
+            ["1.00", "1.25", "1.50"]
+        );
+        assert_eq!(
+            auto.format_ticks(&[-0.5, -0.0, 0.5]),
+            ["-0.5", "0.0", "0.5"]
+        );
+        assert_eq!(auto.format_ticks(&[0., 1e6, 2e6]), ["0", "1e6", "2e6"]);
+        assert_eq!(auto.format_ticks(&[2.5e-5, 5e-5]), ["2.5e-5", "5.0e-5"]);
+        assert_eq!(auto.format_ticks(&[7.]), ["7"]);
+    }
+
+    #[test]
+    fn test_tick_formats() {
+        let ticks = [0., 1500., 2_000_000.];
+        assert_eq!(
+            TickFormat::Fixed(1).format_ticks(&ticks),
+            ["0.0", "1500.0", "2000000.0"]
+        );
+        assert_eq!(
+            TickFormat::Scientific(2).format_ticks(&ticks),
+            ["0.00", "1.50e3", "2.00e6"]
+        );
+        assert_eq!(TickFormat::SI.format_ticks(&ticks), ["0", "1.5k", "2M"]);
+        assert_eq!(TickFormat::SI.format_ticks(&[0.002, -3e9]), ["2m", "-3G"]);
+        assert_eq!(
+            TickFormat::Thousands.format_ticks(&[-1500., 0., 1_234_567.]),
+            ["-1,500", "0", "1,234,567"]
+        );
+        assert_eq!(
+            TickFormat::Percent(0).format_ticks(&[0., 0.25, 1.]),
+            ["0%", "25%", "100%"]
+        );
+        assert_eq!(
+            TickFormat::custom(|t| format!("£{}", t)).format_ticks(&[3.]),
+            ["£3"]
+        );
+    }
+
+    #[test]
+    fn test_axis_tick_format() {
+        let axis = ContinuousAxis::new(0., 1., 3).tick_format(TickFormat::Percent(0));
+        assert_eq!(axis.tick_label(0.5), "50%");
+        assert_eq!(axis.tick_label(0.75), "75%");
+    }
+
+    #[test]
+    fn test_explicit_ticks() {
+        let axis = ContinuousAxis::new(0., 10., 6).tick_positions(&[1., 2.5, 99.]);
+        assert_eq!(axis.ticks(), &[1., 2.5]);
+        assert_eq!(axis.tick_label(2.5), "2.5");
+
+        let axis = ContinuousAxis::new(0., 2., 6).labelled_ticks(&[(0., "Mon"), (1., "Tue")]);
+        assert_eq!(axis.ticks(), &[0., 1.]);
+        assert_eq!(axis.tick_label(1.), "Tue");
+    }
+
+    #[test]
+    fn test_minor_ticks() {
+        let axis = ContinuousAxis::new(0., 2.5, 3).minor_ticks(2);
+        assert_eq!(axis.ticks(), &[0., 1., 2.]);
+        assert_eq!(axis.get_minor_ticks(), &[0.5, 1.5, 2.5]);
+
+        let axis =
+            ContinuousAxis::with_transform(1., 100., 6, Rc::new(Log::new(10.))).minor_ticks(9);
+        assert_eq!(axis.get_minor_ticks().len(), 16);
+        assert_eq!(axis.get_minor_ticks()[..3], [2., 3., 4.]);
+        assert_eq!(axis.get_minor_ticks()[8..11], [20., 30., 40.]);
+
+        let axis = ContinuousAxis::new(0., 1., 6)
+            .tick_positions(&[0.5])
+            .minor_ticks(4);
+        assert!(axis.get_minor_ticks().is_empty());
+    }
 }
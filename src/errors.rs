@@ -10,6 +10,28 @@ pub enum Error {
         lower: f64,
         upper: f64,
     },
+    #[error("Cannot show the value {value} on the {name:?} axis as it is outside the domain of the axis transform")]
+    InvalidAxisValue { name: String, value: f64 },
+    #[error("Cannot use the transform of the {name:?} axis: {reason}")]
+    InvalidAxisTransform { name: String, reason: String },
+    #[error("Cannot draw {errors} {name:?} error bars for {points} data points")]
+    InvalidErrorBars {
+        name: String,
+        errors: usize,
+        points: usize,
+    },
+    #[error("Cannot end whiskers at the {lower} and {upper} percentiles, as they must be outside of the box")]
+    InvalidWhiskers { lower: f64, upper: f64 },
+    #[error("Cannot estimate a density with a bandwidth of {0}, as it must be positive")]
+    InvalidBandwidth(f64),
+    #[error("Cannot lay out the views on the page: {0}")]
+    InvalidLayout(String),
+    #[error("Cannot render a page with no views")]
+    EmptyPage,
+    #[error("Cannot save page as {0:?}")]
+    UnsupportedFormat(String),
+    #[error("Cannot render page: {0}")]
+    FailedToRender(String),
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
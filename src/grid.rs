@@ -28,12 +28,35 @@
 //!
 //! // Render plot
 //! ```
+//!
+//! Grid lines can instead follow the ticks on the axes,
+//! with the lines at the minor ticks drawn in their own style:
+//!
+//! ```rust
+//! # use plotlib::view::ContinuousView;
+//! use plotlib::grid::Grid;
+//! use plotlib::style::LineStyle;
+//! # use plotlib::view::View;
+//!
+//! let mut v = ContinuousView::new().x_minor_ticks(5);
+//! v.add_grid(
+//!     Grid::at_ticks()
+//!         .include_minor(true)
+//!         .minor_style(LineStyle::new().colour("grey").dash(&[2., 2.]).opacity(0.5)),
+//! );
+//! ```
+
+use crate::style::LineStyle;
 
-// Internal type representing the logic of when do we render only horizontal lines, and when do we
-// render a full grid
-pub(crate) enum GridType<'a> {
-    HorizontalOnly(&'a Grid),
-    Both(&'a Grid),
+/// Where the lines of a grid are placed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridMode {
+    /// `nx` and `ny` lines evenly spaced across the face
+    Even,
+    /// A line at each major tick of the axes
+    MajorTicks,
+    /// A line at each major and minor tick of the axes
+    AllTicks,
 }
 
 /// Configuration for the grid on a plot
@@ -45,8 +68,15 @@ pub struct Grid {
     pub nx: u32,
     /// Number of horizontal grid lines (defaults to 3)
     pub ny: u32,
-    /// Color of the grid lines (defaults to "darkgrey")
+    /// Color of the grid lines (defaults to "darkgrey"),
+    /// used unless `major_style` sets a colour
     pub color: String,
+    /// Where the grid lines are placed (defaults to `GridMode::Even`)
+    pub mode: GridMode,
+    /// The style of the lines evenly spaced or at the major ticks
+    pub major_style: LineStyle,
+    /// The style of the lines at the minor ticks (defaults to thin light grey lines)
+    pub minor_style: LineStyle,
 }
 
 impl Default for Grid {
@@ -64,6 +94,48 @@ impl Grid {
             nx,
             ny,
             color: "darkgrey".to_owned(),
+            mode: GridMode::Even,
+            major_style: LineStyle::new(),
+            minor_style: LineStyle::new().colour("lightgrey").width(0.5),
+        }
+    }
+
+    /// Create a new grid with a line at each of the major ticks of the axes
+    pub fn at_ticks() -> Grid {
+        Grid {
+            mode: GridMode::MajorTicks,
+            ..Grid::default()
         }
     }
+
+    /// Whether to also draw lines at the minor ticks of the axes
+    ///
+    /// This switches the grid to following the ticks, if it did not already.
+    pub fn include_minor(mut self, minor: bool) -> Self {
+        self.mode = if minor {
+            GridMode::AllTicks
+        } else {
+            GridMode::MajorTicks
+        };
+        self
+    }
+
+    /// Set the style of the major grid lines
+    pub fn major_style(mut self, style: LineStyle) -> Self {
+        self.major_style = style;
+        self
+    }
+
+    /// Set the style of the minor grid lines
+    pub fn minor_style(mut self, style: LineStyle) -> Self {
+        self.minor_style = style;
+        self
+    }
+
+    /// The style of the major grid lines, with the grid colour filled in
+    pub(crate) fn get_major_style(&self) -> LineStyle {
+        let mut style = LineStyle::new().colour(self.color.as_str());
+        style.overlay(&self.major_style);
+        style
+    }
 }
@@ -0,0 +1,151 @@
+//! Configure the legend of a view.
+//!
+//! A legend lists each representation which has a label, with a key showing its style.
+//! Where it goes and how it looks is set by giving the view a `Legend`:
+//!
+//! ```rust
+//! use plotlib::legend::{Legend, LegendPosition};
+//! use plotlib::repr::Plot;
+//! use plotlib::style::{LineStyle, PointStyle};
+//! use plotlib::view::ContinuousView;
+//!
+//! let p = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2)])
+//!     .point_style(PointStyle::new())
+//!     .legend("Measurements".to_string());
+//!
+//! let v = ContinuousView::new().add(p).legend(
+//!     Legend::new()
+//!         .position(LegendPosition::OutsideRight)
+//!         .title("Key")
+//!         .frame(LineStyle::new().colour("grey"))
+//!         .background("white"),
+//! );
+//! ```
+//!
+//! Legends placed outside the face are given their own space when the view is laid out on a page.
+
+use crate::style::LineStyle;
+
+/// Where the legend is placed relative to the face of the view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LegendPosition {
+    /// In the top-left corner of the face
+    TopLeft,
+    /// In the top-right corner of the face
+    TopRight,
+    /// In the bottom-left corner of the face
+    BottomLeft,
+    /// In the bottom-right corner of the face
+    BottomRight,
+    /// In whichever corner of the face covers the least of the data
+    Best,
+    /// To the right of the face, beyond any secondary y-axis
+    OutsideRight,
+    /// Below the face, beneath the x-axis
+    OutsideBottom,
+}
+
+impl LegendPosition {
+    /// Whether the legend sits outside of the face, needing space of its own
+    pub fn is_outside(self) -> bool {
+        matches!(
+            self,
+            LegendPosition::OutsideRight | LegendPosition::OutsideBottom
+        )
+    }
+}
+
+/// Configuration for the legend of a view
+///
+/// By default the legend sits in the bottom-right corner of the face,
+/// in a single column with no title, frame or background.
+#[derive(Debug, Clone)]
+pub struct Legend {
+    position: LegendPosition,
+    columns: usize,
+    title: Option<String>,
+    frame: Option<LineStyle>,
+    background: Option<String>,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Legend::new()
+    }
+}
+
+impl Legend {
+    /// Create a legend with the default settings
+    pub fn new() -> Self {
+        Legend {
+            position: LegendPosition::BottomRight,
+            columns: 1,
+            title: None,
+            frame: None,
+            background: None,
+        }
+    }
+
+    /// Set where the legend is placed
+    pub fn position(mut self, position: LegendPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the number of columns the entries are split into
+    ///
+    /// The entries fill each column in turn, from top to bottom.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    /// Set a title shown above the entries
+    pub fn title<T>(mut self, title: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Draw a frame around the legend in the given style
+    pub fn frame(mut self, style: LineStyle) -> Self {
+        self.frame = Some(style);
+        self
+    }
+
+    /// Fill the area behind the legend with a colour, hiding anything underneath it
+    pub fn background<T>(mut self, colour: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.background = Some(colour.into());
+        self
+    }
+
+    /// Where the legend is placed
+    pub fn get_position(&self) -> LegendPosition {
+        self.position
+    }
+
+    /// The number of columns the entries are split into
+    pub fn get_columns(&self) -> usize {
+        self.columns
+    }
+
+    /// The title shown above the entries, if there is one
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The style of the frame around the legend, if there is one
+    pub fn get_frame(&self) -> Option<&LineStyle> {
+        self.frame.as_ref()
+    }
+
+    /// The colour filling the area behind the legend, if there is one
+    pub fn get_background(&self) -> Option<&str> {
+        self.background.as_deref()
+    }
+}
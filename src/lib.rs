@@ -73,14 +73,18 @@ in this case, interpreting the bins and colours to create SVG elements.
 
 */
 
+pub mod axis;
 pub mod grid;
+pub mod legend;
 pub mod page;
+pub mod render;
 pub mod repr;
 pub mod style;
 pub mod view;
 
-mod axis;
 mod errors;
+mod pdf_render;
 mod svg_render;
 mod text_render;
+mod time;
 mod utils;
@@ -6,20 +6,84 @@ use crate::errors;
 use std::ffi::OsStr;
 use std::path::Path;
 
-use svg;
 use svg::Document;
 use svg::Node;
 
 use crate::errors::Result;
+use crate::pdf_render::{PdfPage, PdfSurface};
+use crate::render::{Surface, TextAnchor};
+use crate::svg_render::SvgSurface;
+use crate::text_render;
 use crate::view::View;
 
+const TITLE_FONT_SIZE: f64 = 18.;
+const SUBTITLE_FONT_SIZE: f64 = 13.;
+const CAPTION_FONT_SIZE: f64 = 11.;
+
+/**
+The space left around the face of a view for its axes, labels and title
+
+Each is given in the same units as the page dimensions.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Margins {
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+    pub bottom: f64,
+}
+
+impl Margins {
+    pub fn new(left: f64, right: f64, top: f64, bottom: f64) -> Self {
+        Margins {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+}
+
+/// The position of a view in the page grid, in units of grid cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    row: u32,
+    column: u32,
+    row_span: u32,
+    column_span: u32,
+}
+
+/// Where a view is drawn on the page, in pixels
+struct Face<'a> {
+    view: &'a dyn View,
+    /// The bottom-left corner of the face, measured from the bottom left of the page
+    origin: (f64, f64),
+    size: (f64, f64),
+}
+
+impl Placement {
+    fn overlaps(&self, other: &Placement) -> bool {
+        self.row < other.row + other.row_span
+            && other.row < self.row + self.row_span
+            && self.column < other.column + other.column_span
+            && other.column < self.column + self.column_span
+    }
+}
+
 /**
 A single page page laying out the views in a grid
 */
 pub struct Page<'a> {
     views: Vec<&'a dyn View>,
+    placements: Vec<Option<Placement>>,
     num_views: u32,
     dimensions: (u32, u32),
+    layout: Option<(u32, u32)>,
+    dpi: f32,
+    title: Option<String>,
+    subtitle: Option<String>,
+    caption: Option<String>,
+    margins: Option<Margins>,
 }
 
 impl<'a> Page<'a> {
@@ -29,8 +93,15 @@ impl<'a> Page<'a> {
     pub fn empty() -> Self {
         Page {
             views: Vec::new(),
+            placements: Vec::new(),
             num_views: 0,
             dimensions: (600, 400),
+            layout: None,
+            dpi: 96.,
+            title: None,
+            subtitle: None,
+            caption: None,
+            margins: None,
         }
     }
 
@@ -47,72 +118,689 @@ impl<'a> Page<'a> {
         self
     }
 
-    /// Add a view to the plot
+    /**
+    Set the resolution used when rasterising the page, e.g. to PNG.
+
+    The dimensions of the page are taken to be at 96 DPI
+    so the default of 96 gives one pixel per unit of the dimensions.
+    */
+    pub fn dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /**
+    Set the number of rows and columns of the grid that the views are laid out in.
+
+    If this is not set, a grid just big enough to hold all the views is used.
+    */
+    pub fn layout(mut self, rows: u32, columns: u32) -> Self {
+        self.layout = Some((rows, columns));
+        self
+    }
+
+    /// Set the title shown across the top of the page, above all of the views
+    pub fn title<S>(mut self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set a subtitle, shown in smaller text beneath the title
+    pub fn subtitle<S>(mut self, subtitle: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Set a caption, shown across the bottom of the page beneath all of the views
+    pub fn caption<S>(mut self, caption: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /**
+    Set the margins around the face of every view, rather than sizing them automatically.
+
+    By default each view is measured and given just enough space for its
+    tick labels, axis labels and title.
+    */
+    pub fn margins(mut self, margins: Margins) -> Self {
+        self.margins = Some(margins);
+        self
+    }
+
+    /// Add a view to the plot, placed in the next free cell of the grid
     pub fn add_plot(mut self, view: &'a dyn View) -> Self {
         self.views.push(view);
+        self.placements.push(None);
+        self.num_views += 1;
+        self
+    }
+
+    /// Add a view to the plot in the cell at `row` and `column` (counting from zero)
+    pub fn add_plot_at(self, view: &'a dyn View, row: u32, column: u32) -> Self {
+        self.add_plot_spanning(view, row, column, 1, 1)
+    }
+
+    /**
+    Add a view to the plot, starting in the cell at `row` and `column` (counting from zero)
+    and stretching over `row_span` rows and `column_span` columns
+    */
+    pub fn add_plot_spanning(
+        mut self,
+        view: &'a dyn View,
+        row: u32,
+        column: u32,
+        row_span: u32,
+        column_span: u32,
+    ) -> Self {
+        self.views.push(view);
+        self.placements.push(Some(Placement {
+            row,
+            column,
+            row_span,
+            column_span,
+        }));
         self.num_views += 1;
         self
     }
 
+    /// The number of rows and columns in the grid
+    fn grid_size(&self) -> (u32, u32) {
+        if let Some(layout) = self.layout {
+            return layout;
+        }
+        let columns = (f64::from(self.num_views).sqrt().ceil() as u32).max(1);
+        let rows = self.num_views.div_ceil(columns).max(1);
+        // Make sure that any explicitly placed views fit too
+        self.placements
+            .iter()
+            .flatten()
+            .fold((rows, columns), |(rows, columns), p| {
+                (
+                    rows.max(p.row + p.row_span),
+                    columns.max(p.column + p.column_span),
+                )
+            })
+    }
+
+    /// Work out which cells of the grid each view occupies
+    fn place_views(&self) -> Result<((u32, u32), Vec<Placement>)> {
+        let (rows, columns) = self.grid_size();
+        let mut placed: Vec<Placement> = vec![];
+
+        for p in self.placements.iter().flatten() {
+            if p.row_span == 0 || p.column_span == 0 {
+                return Err(errors::Error::InvalidLayout(
+                    "a view must span at least one row and one column".into(),
+                ));
+            }
+            if p.row + p.row_span > rows || p.column + p.column_span > columns {
+                return Err(errors::Error::InvalidLayout(format!(
+                    "a view placed at row {}, column {} does not fit in a {}x{} grid",
+                    p.row, p.column, rows, columns
+                )));
+            }
+            if placed.iter().any(|other| other.overlaps(p)) {
+                return Err(errors::Error::InvalidLayout(format!(
+                    "the view placed at row {}, column {} overlaps another view",
+                    p.row, p.column
+                )));
+            }
+            placed.push(*p);
+        }
+
+        // The remaining views fill up the free cells, row by row
+        let mut free_cells = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .map(|(row, column)| Placement {
+                row,
+                column,
+                row_span: 1,
+                column_span: 1,
+            })
+            .filter(|cell| !placed.iter().any(|p| p.overlaps(cell)))
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let mut placements = vec![];
+        for p in &self.placements {
+            match p {
+                Some(p) => placements.push(*p),
+                None => match free_cells.next() {
+                    Some(cell) => placements.push(cell),
+                    None => {
+                        return Err(errors::Error::InvalidLayout(format!(
+                            "there is no space left in a {}x{} grid for {} views",
+                            rows, columns, self.num_views
+                        )))
+                    }
+                },
+            }
+        }
+
+        Ok(((rows, columns), placements))
+    }
+
+    /// Draw the title and subtitle at the top of the page and the caption at the bottom,
+    /// onto a surface covering the whole page.
+    ///
+    /// Returns the space they take at the top and bottom of the page.
+    fn draw_titles(&self, surface: &mut dyn Surface) -> (f64, f64) {
+        let (width, height) = surface.size();
+        let mut header_height = 0.;
+        if let Some(ref title) = self.title {
+            header_height += 30.;
+            surface.text(
+                (width / 2., height - header_height + 6.),
+                title,
+                TextAnchor::Middle,
+                TITLE_FONT_SIZE,
+            );
+        }
+        if let Some(ref subtitle) = self.subtitle {
+            header_height += 20.;
+            surface.text(
+                (width / 2., height - header_height + 5.),
+                subtitle,
+                TextAnchor::Middle,
+                SUBTITLE_FONT_SIZE,
+            );
+        }
+        let footer_height = if let Some(ref caption) = self.caption {
+            surface.text(
+                (width / 2., 8.),
+                caption,
+                TextAnchor::Middle,
+                CAPTION_FONT_SIZE,
+            );
+            24.
+        } else {
+            0.
+        };
+        (header_height, footer_height)
+    }
+
+    /// Lay out the faces of the views in the space between the titles and the caption
+    fn layout_faces(&self, (header_height, footer_height): (f64, f64)) -> Result<Vec<Face<'a>>> {
+        let (width, height) = self.dimensions;
+        let ((rows, columns), placements) = self.place_views()?;
+        let cell_width = f64::from(width) / f64::from(columns);
+        let cell_height = (f64::from(height) - header_height - footer_height) / f64::from(rows);
+
+        let mut faces = vec![];
+        for (&view, p) in self.views.iter().zip(placements) {
+            // Each view fits its face, and the margins around it, within its own cells
+            let view_width = f64::from(p.column_span) * cell_width;
+            let view_height = f64::from(p.row_span) * cell_height;
+            let margins = match self.margins {
+                Some(margins) => margins,
+                None => view.margins(view_width)?,
+            };
+            let face_width = view_width - margins.left - margins.right;
+            let face_height = view_height - margins.top - margins.bottom;
+            if face_width <= 0. || face_height <= 0. {
+                return Err(errors::Error::InvalidLayout(format!(
+                    "the page is too small to fit a {}x{} grid of views",
+                    rows, columns
+                )));
+            }
+
+            let cell_x = f64::from(p.column) * cell_width;
+            let cell_bottom = footer_height + f64::from(rows - p.row - p.row_span) * cell_height;
+            faces.push(Face {
+                view,
+                origin: (cell_x + margins.left, cell_bottom + margins.bottom),
+                size: (face_width, face_height),
+            });
+        }
+        Ok(faces)
+    }
+
     /**
     Render the plot to an svg document
     */
     pub fn to_svg(&self) -> Result<svg::Document> {
         let (width, height) = self.dimensions;
-        let mut document = Document::new().set("viewBox", (0, 0, width, height));
-
-        let x_margin = 120; // should actually depend on y-axis label font size
-        let y_margin = 60;
-        let x_offset = 0.6 * f64::from(x_margin);
-        let y_offset = 0.6 * f64::from(y_margin);
-
-        // TODO put multiple views in correct places
-        for &view in &self.views {
-            let view_group = view
-                .to_svg(f64::from(width - x_margin), f64::from(height - y_margin))?
-                .set(
-                    "transform",
-                    format!("translate({}, {})", x_offset, f64::from(height) - y_offset),
-                );
-            document.append(view_group);
+        let (width, height) = (f64::from(width), f64::from(height));
+        let mut document = Document::new().set("viewBox", (0., 0., width, height));
+
+        // SVG measures down from the top of the page, so each surface is moved up from the bottom
+        let place = |group: svg::node::element::Group, (x, y): (f64, f64)| {
+            group.set("transform", format!("translate({}, {})", x, height - y))
+        };
+
+        let mut titles = SvgSurface::with_clip_ids(width, height, 0);
+        let title_space = self.draw_titles(&mut titles);
+        if title_space != (0., 0.) {
+            document.append(place(titles.into_group(), (0., 0.)));
+        }
+
+        // The clip regions are numbered through the whole document, so each view carries on
+        // from the last and the same page always renders the same way
+        let mut clip_id = 0;
+        for face in self.layout_faces(title_space)? {
+            let mut surface = SvgSurface::with_clip_ids(face.size.0, face.size.1, clip_id);
+            face.view.draw(&mut surface)?;
+            clip_id = surface.next_clip_id();
+            document.append(place(surface.into_group(), face.origin));
         }
         Ok(document)
     }
 
     /**
     Render the plot to an `String`
+
+    For text output, the dimensions give the size of the grid of faces in characters.
+    Each view is given its share of that and then the axes are added around it.
     */
     pub fn to_text(&self) -> Result<String> {
+        if self.views.is_empty() {
+            return Err(errors::Error::EmptyPage);
+        }
+
         let (width, height) = self.dimensions;
-        // TODO compose multiple views into a page
-        let view = self.views[0];
-        view.to_text(width, height)
+        let ((rows, columns), placements) = self.place_views()?;
+        let cell_width = width / columns;
+        let cell_height = height / rows;
+
+        let mut renders = vec![];
+        for (&view, p) in self.views.iter().zip(&placements) {
+            let view_string = view.to_text(p.column_span * cell_width, p.row_span * cell_height)?;
+            renders.push(view_string);
+        }
+
+        // Size each row and column of the grid by the largest view which sits only in it
+        let mut column_widths = vec![cell_width as usize; columns as usize];
+        let mut row_heights = vec![cell_height as usize; rows as usize];
+        for (render, p) in renders.iter().zip(&placements) {
+            let render_width = render.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+            let render_height = render.lines().count();
+            if p.column_span == 1 {
+                let w = &mut column_widths[p.column as usize];
+                *w = (*w).max(render_width);
+            }
+            if p.row_span == 1 {
+                let h = &mut row_heights[p.row as usize];
+                *h = (*h).max(render_height);
+            }
+        }
+
+        // Leave a gap between neighbouring views
+        let column_gap = 2;
+        let row_gap = 1;
+        let column_offsets: Vec<usize> = column_widths
+            .iter()
+            .scan(0, |offset, w| {
+                let this = *offset;
+                *offset += w + column_gap;
+                Some(this)
+            })
+            .collect();
+        let row_offsets: Vec<usize> = row_heights
+            .iter()
+            .scan(0, |offset, h| {
+                let this = *offset;
+                *offset += h + row_gap;
+                Some(this)
+            })
+            .collect();
+
+        let mut page_width = 0;
+        let mut page_height = 0;
+        for (render, p) in renders.iter().zip(&placements) {
+            let render_width = render.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+            page_width = page_width.max(column_offsets[p.column as usize] + render_width);
+            page_height = page_height.max(row_offsets[p.row as usize] + render.lines().count());
+        }
+
+        let mut page_string = text_render::empty_face(page_width as u32, page_height as u32);
+        for (render, p) in renders.iter().zip(&placements) {
+            page_string = text_render::overlay(
+                &page_string,
+                render,
+                column_offsets[p.column as usize] as i32,
+                row_offsets[p.row as usize] as i32,
+            );
+        }
+
+        let header: Vec<String> = self
+            .title
+            .iter()
+            .chain(&self.subtitle)
+            .map(|line| text_render::centred_line(line, page_width))
+            .collect();
+        if !header.is_empty() {
+            page_string = format!("{}\n\n{}", header.join("\n"), page_string);
+        }
+        if let Some(ref caption) = self.caption {
+            page_string = format!(
+                "{}\n\n{}",
+                page_string,
+                text_render::centred_line(caption, page_width)
+            );
+        }
+
+        Ok(page_string)
+    }
+
+    /**
+    Render the plot to a PDF document, returned as the bytes of the file
+
+    The page is a vector image, one point for each pixel of the dimensions at 96 DPI.
+    Text is set in Helvetica, one of the standard fonts that every PDF reader provides.
+    */
+    pub fn to_pdf(&self) -> Result<Vec<u8>> {
+        let (width, height) = self.dimensions;
+        let (width, height) = (f64::from(width), f64::from(height));
+        let mut page = PdfPage::new(width, height);
+
+        let mut titles = PdfSurface::new(&mut page, (0., 0.), (width, height));
+        let title_space = self.draw_titles(&mut titles);
+        titles.finish();
+
+        for face in self.layout_faces(title_space)? {
+            let mut surface = PdfSurface::new(&mut page, face.origin, face.size);
+            face.view.draw(&mut surface)?;
+            surface.finish();
+        }
+        Ok(page.into_pdf())
+    }
+
+    /**
+    Render the plot to a PNG image, returned as the bytes of the encoded file
+
+    The size of the image is given by the dimensions, scaled by the DPI setting.
+    This is only available with the `png` feature enabled.
+    */
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        use resvg::{tiny_skia, usvg};
+
+        let mut options = usvg::Options::default();
+        let fonts = options.fontdb_mut();
+        fonts.load_system_fonts();
+        // The SVG doesn't ask for a particular font so pick one we know exists, preferring sans
+        if let Some(family) = fonts
+            .faces()
+            .filter_map(|face| face.families.first())
+            .map(|(family, _)| family.clone())
+            .min_by_key(|family| !family.contains("Sans"))
+        {
+            options.font_family = family;
+        }
+
+        let tree = usvg::Tree::from_str(&self.to_svg()?.to_string(), &options)
+            .map_err(|e| errors::Error::FailedToRender(e.to_string()))?;
+
+        let scale = self.dpi / 96.;
+        let (width, height) = self.dimensions;
+        let mut pixmap = tiny_skia::Pixmap::new(
+            (width as f32 * scale).round() as u32,
+            (height as f32 * scale).round() as u32,
+        )
+        .ok_or_else(|| errors::Error::FailedToRender("invalid image size".into()))?;
+        pixmap.fill(tiny_skia::Color::WHITE);
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        pixmap
+            .encode_png()
+            .map_err(|e| errors::Error::FailedToRender(e.to_string()))
     }
 
     /**
     Save the plot to a file.
 
     The type of file will be based on the file extension.
+    Supported are `.svg`, `.pdf` and, with the `png` feature enabled, `.png`.
     */
     pub fn save<P>(&self, path: P) -> Result<()>
     where
         P: AsRef<Path>,
     {
         let path = path.as_ref();
-        match path.extension().and_then(OsStr::to_str) {
-            Some("svg") => {
-                if let Some(parent) = path.parent() {
-                    if !parent.exists() {
-                        std::fs::create_dir_all(parent)?;
-                    }
-                }
+        let contents = match path.extension().and_then(OsStr::to_str) {
+            Some("svg") => self.to_svg()?.to_string().into_bytes(),
+            Some("pdf") => self.to_pdf()?,
+            #[cfg(feature = "png")]
+            Some("png") => self.to_png()?,
+            #[cfg(not(feature = "png"))]
+            Some("png") => {
+                return Err(errors::Error::UnsupportedFormat(
+                    "png (enable the `png` feature)".into(),
+                ))
+            }
+            other => {
+                return Err(errors::Error::UnsupportedFormat(
+                    other.unwrap_or_default().into(),
+                ))
+            }
+        };
 
-                match svg::save(&path, &self.to_svg()?) {
-                    Ok(ok) => Ok(ok),
-                    Err(error) => Err(errors::Error::FailedToSave(error))
-                }
-            },
-            _ => Ok(()),
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
         }
+
+        std::fs::write(path, contents).map_err(errors::Error::FailedToSave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::ContinuousView;
+
+    fn placement(row: u32, column: u32, row_span: u32, column_span: u32) -> Placement {
+        Placement {
+            row,
+            column,
+            row_span,
+            column_span,
+        }
+    }
+
+    #[test]
+    fn test_automatic_layout() {
+        let v = ContinuousView::new();
+        let page = Page::single(&v);
+        let (grid, placements) = page.place_views().unwrap();
+        assert_eq!(grid, (1, 1));
+        assert_eq!(placements, [placement(0, 0, 1, 1)]);
+
+        let page = Page::empty()
+            .add_plot(&v)
+            .add_plot(&v)
+            .add_plot(&v)
+            .add_plot(&v)
+            .add_plot(&v);
+        let (grid, placements) = page.place_views().unwrap();
+        assert_eq!(grid, (2, 3));
+        assert_eq!(placements[2], placement(0, 2, 1, 1));
+        assert_eq!(placements[4], placement(1, 1, 1, 1));
+    }
+
+    #[test]
+    fn test_layout_with_spans() {
+        let v = ContinuousView::new();
+        let page = Page::empty()
+            .layout(2, 2)
+            .add_plot(&v)
+            .add_plot_spanning(&v, 0, 1, 2, 1)
+            .add_plot(&v);
+        let (grid, placements) = page.place_views().unwrap();
+        assert_eq!(grid, (2, 2));
+        assert_eq!(
+            placements,
+            [
+                placement(0, 0, 1, 1),
+                placement(0, 1, 2, 1),
+                placement(1, 0, 1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_layouts() {
+        let v = ContinuousView::new();
+        let overlapping = Page::empty()
+            .add_plot_spanning(&v, 0, 0, 2, 2)
+            .add_plot_at(&v, 1, 1);
+        assert!(overlapping.place_views().is_err());
+
+        let outside = Page::empty().layout(1, 2).add_plot_at(&v, 0, 2);
+        assert!(outside.place_views().is_err());
+
+        let too_many = Page::empty().layout(1, 1).add_plot(&v).add_plot(&v);
+        assert!(too_many.place_views().is_err());
+    }
+
+    #[test]
+    fn test_save_unsupported_format() {
+        let v = ContinuousView::new().x_range(0., 1.).y_range(0., 1.);
+        assert!(Page::single(&v).save("target/page.unknown").is_err());
+        assert!(!Path::new("target/page.unknown").exists());
+    }
+
+    #[test]
+    fn test_to_pdf() {
+        let v = ContinuousView::new()
+            .x_range(0., 1.)
+            .y_range(0., 1.)
+            .x_label("Some label");
+        let pdf = Page::single(&v).dimensions(400, 300).to_pdf().unwrap();
+        let pdf = String::from_utf8(pdf).unwrap();
+        assert!(pdf.starts_with("%PDF-"));
+        assert!(pdf.contains("/MediaBox [0 0 300 225]"));
+        assert!(pdf.contains("(Some label) Tj"));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_to_png() {
+        let v = ContinuousView::new().x_range(0., 1.).y_range(0., 1.);
+        let png = Page::single(&v)
+            .dimensions(300, 200)
+            .dpi(192.)
+            .to_png()
+            .unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        // The width and height are the first entries in the IHDR chunk
+        assert_eq!(&png[16..24], [0, 0, 2, 88, 0, 0, 1, 144]);
+    }
+
+    #[test]
+    fn test_empty_page_to_text() {
+        assert!(Page::empty().to_text().is_err());
+    }
+
+    #[test]
+    fn test_multiple_views_to_text() {
+        use crate::repr::Plot;
+        use crate::style::PointStyle;
+
+        let p1 = Plot::new(vec![(0., 1.), (2., 3.)]).point_style(PointStyle::new());
+        let p2 = Plot::new(vec![(0., 10.), (3., 20.)]).point_style(PointStyle::new());
+        let v1 = ContinuousView::new().add(p1);
+        let v2 = ContinuousView::new().add(p2);
+
+        let single = v1.to_text(30, 10).unwrap();
+        let page = Page::single(&v1).dimensions(30, 10).to_text().unwrap();
+        assert_eq!(page, single);
+
+        let side_by_side = Page::empty()
+            .add_plot(&v1)
+            .add_plot(&v2)
+            .dimensions(60, 10)
+            .to_text()
+            .unwrap();
+        assert_eq!(side_by_side.lines().count(), single.lines().count());
+        assert!(side_by_side.lines().next().unwrap().chars().count() > 60);
+        assert!(side_by_side.contains("20"));
+
+        let stacked = Page::empty()
+            .layout(2, 1)
+            .add_plot(&v1)
+            .add_plot(&v2)
+            .dimensions(30, 20)
+            .to_text()
+            .unwrap();
+        assert_eq!(stacked.lines().count(), 2 * single.lines().count() + 1);
+    }
+
+    #[test]
+    fn test_titles() {
+        use crate::repr::Plot;
+        use crate::style::PointStyle;
+
+        let p = Plot::new(vec![(0., 1.), (2., 3.)]).point_style(PointStyle::new());
+        let v = ContinuousView::new().add(p).title("View title");
+        let page = Page::single(&v)
+            .title("Page title")
+            .subtitle("Subtitle")
+            .caption("Caption");
+
+        let svg = page.to_svg().unwrap().to_string();
+        for text in &["Page title", "Subtitle", "Caption", "View title"] {
+            assert!(svg.contains(&format!("\n{}\n", text)), "missing {:?}", text);
+        }
+
+        let text = page.dimensions(30, 10).to_text().unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0].trim(), "Page title");
+        assert_eq!(lines[1].trim(), "Subtitle");
+        assert_eq!(lines[3].trim(), "View title");
+        assert_eq!(lines.last().unwrap().trim(), "Caption");
+        // The titles are centred
+        assert!(lines[0].starts_with("  "));
+    }
+
+    #[test]
+    fn test_margins() {
+        use crate::repr::Plot;
+        use crate::style::PointStyle;
+        use crate::view::View;
+
+        let p = || Plot::new(vec![(0., 1.), (2., 3.)]).point_style(PointStyle::new());
+        let short = ContinuousView::new().add(p());
+        let long = ContinuousView::new()
+            .add(p())
+            .y_labelled_ticks(&[(1., "a very long tick label")])
+            .y_label("y");
+        let short_margins = short.margins(600.).unwrap();
+        let long_margins = long.margins(600.).unwrap();
+        assert!(long_margins.left > short_margins.left + 100.);
+        assert_eq!(long_margins.bottom, short_margins.bottom);
+        assert!(short.title("Title").margins(600.).unwrap().top > short_margins.top);
+
+        // The face is moved over to make room for the wider labels
+        let translate = |svg: &str| {
+            let start = svg.find("translate(").unwrap() + "translate(".len();
+            let end = start + svg[start..].find(',').unwrap();
+            svg[start..end].parse::<f64>().unwrap()
+        };
+        let svg = Page::single(&long).to_svg().unwrap().to_string();
+        assert_eq!(translate(&svg), long_margins.left);
+
+        // Explicit margins are used as given
+        let svg = Page::single(&long)
+            .margins(Margins::new(50., 10., 10., 40.))
+            .to_svg()
+            .unwrap()
+            .to_string();
+        assert_eq!(translate(&svg), 50.);
     }
 }
@@ -0,0 +1,661 @@
+//! A module for rendering pages to PDF
+//!
+//! Each view is drawn onto a [`PdfSurface`](struct.PdfSurface.html) covering its face,
+//! which writes PDF drawing operators straight into the content stream of the page.
+//! All text is set in Helvetica, one of the standard PDF fonts,
+//! so the output does not depend on what fonts happen to be installed.
+
+use std::fmt::Write;
+
+use crate::render::{text_width, Surface, TextAnchor};
+use crate::style::{BoxStyle, LineJoin, LineStyle, PointMarker, PointStyle};
+
+/// The number of PDF points per pixel (72 points per inch, 96 pixels per inch)
+const POINTS_PER_PIXEL: f64 = 0.75;
+
+/// The CSS named colours, as used by SVG
+const NAMED_COLOURS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+/// Parse an SVG colour into its RGB components, each between 0 and 1
+///
+/// Returns `None` for `none` and for anything which isn't understood.
+fn parse_colour(colour: &str) -> Option<(f64, f64, f64)> {
+    let colour = colour.trim().to_lowercase();
+    let rgb = if let Some(hex) = colour.strip_prefix('#') {
+        match hex.len() {
+            3 => {
+                let short = u32::from_str_radix(hex, 16).ok()?;
+                let (r, g, b) = ((short >> 8) & 0xf, (short >> 4) & 0xf, short & 0xf);
+                ((r * 0x11) << 16) | ((g * 0x11) << 8) | (b * 0x11)
+            }
+            6 => u32::from_str_radix(hex, 16).ok()?,
+            _ => return None,
+        }
+    } else {
+        NAMED_COLOURS
+            .iter()
+            .find(|&&(name, _)| name == colour)
+            .map(|&(_, rgb)| rgb)?
+    };
+    let channel = |shift: u32| f64::from((rgb >> shift) & 0xff) / 255.;
+    Some((channel(16), channel(8), channel(0)))
+}
+
+/// Escape a string for use as a PDF string literal, encoded as WinAnsi
+fn pdf_string(text: &str) -> String {
+    let mut s = String::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                s.push('\\');
+                s.push(c);
+            }
+            ' '..='~' => s.push(c),
+            '\u{a0}'..='\u{ff}' => {
+                let _ = write!(s, "\\{:03o}", c as u32);
+            }
+            _ => s.push('?'),
+        }
+    }
+    s
+}
+
+/// The painting operator for a shape with the given fill and outline colours,
+/// having set the colours, or `None` if nothing would be painted
+fn paint_op(
+    ops: &mut String,
+    fill: Option<(f64, f64, f64)>,
+    stroke: Option<(f64, f64, f64)>,
+) -> Option<&'static str> {
+    if let Some((r, g, b)) = fill {
+        let _ = writeln!(ops, "{} {} {} rg", r, g, b);
+    }
+    if let Some((r, g, b)) = stroke {
+        let _ = writeln!(ops, "{} {} {} RG 1 w", r, g, b);
+    }
+    match (fill.is_some(), stroke.is_some()) {
+        (true, true) => Some("B"),
+        (true, false) => Some("f"),
+        (false, true) => Some("S"),
+        (false, false) => None,
+    }
+}
+
+/// The content stream of a page and the graphics states it uses, drawn onto by `PdfSurface`s
+pub(crate) struct PdfPage {
+    /// The width and height of the page in pixels
+    size: (f64, f64),
+    ops: String,
+    /// The stroke (`CA`) and fill (`ca`) opacities used,
+    /// each of which needs a graphics state in the page resources
+    opacities: Vec<(&'static str, f32)>,
+}
+
+impl PdfPage {
+    pub(crate) fn new(width: f64, height: f64) -> Self {
+        let mut ops = String::new();
+        // Draw in pixels, with the origin at the bottom left of the page
+        let _ = writeln!(ops, "{} 0 0 {} 0 0 cm", POINTS_PER_PIXEL, POINTS_PER_PIXEL);
+        PdfPage {
+            size: (width, height),
+            ops,
+            opacities: vec![],
+        }
+    }
+
+    /// Switch to a graphics state with the given stroke (`CA`) or fill (`ca`) opacity
+    fn set_opacity(&mut self, key: &'static str, opacity: f32) {
+        let index = match self.opacities.iter().position(|&o| o == (key, opacity)) {
+            Some(index) => index,
+            None => {
+                self.opacities.push((key, opacity));
+                self.opacities.len() - 1
+            }
+        };
+        let _ = writeln!(self.ops, "/GS{} gs", index);
+    }
+
+    /// Finish the page and return the bytes of the PDF file
+    pub(crate) fn into_pdf(self) -> Vec<u8> {
+        let page_width = self.size.0 * POINTS_PER_PIXEL;
+        let page_height = self.size.1 * POINTS_PER_PIXEL;
+
+        let graphics_states: String = self
+            .opacities
+            .iter()
+            .enumerate()
+            .map(|(i, (key, opacity))| format!(" /GS{} << /{} {} >>", i, key, opacity))
+            .collect();
+        let graphics_states = if graphics_states.is_empty() {
+            graphics_states
+        } else {
+            format!(" /ExtGState <<{} >>", graphics_states)
+        };
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 5 0 R >>{} >> /Contents 4 0 R >>",
+                page_width, page_height, graphics_states
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                self.ops.len(),
+                self.ops
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        ];
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = vec![];
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+        }
+        let xref_offset = pdf.len();
+        let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            pdf,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        );
+
+        pdf.into_bytes()
+    }
+}
+
+/// A surface which draws onto part of a PDF page, with the origin at the bottom-left corner of the face
+pub(crate) struct PdfSurface<'a> {
+    page: &'a mut PdfPage,
+    size: (f64, f64),
+    /// The number of clip regions pushed, each of which saved the graphics state
+    clips: usize,
+}
+
+impl<'a> PdfSurface<'a> {
+    /// A surface for a face of the given size,
+    /// with its bottom-left corner at `origin` pixels from the bottom left of the page
+    pub(crate) fn new(page: &'a mut PdfPage, origin: (f64, f64), size: (f64, f64)) -> Self {
+        let _ = writeln!(page.ops, "q\n1 0 0 1 {} {} cm", origin.0, origin.1);
+        PdfSurface {
+            page,
+            size,
+            clips: 0,
+        }
+    }
+
+    /// Finish drawing, restoring the graphics state of the page
+    pub(crate) fn finish(mut self) {
+        while self.clips > 0 {
+            self.pop_clip();
+        }
+        self.page.ops.push_str("Q\n");
+    }
+
+    /// Draw a shape, saving the graphics state so that its colours and opacities
+    /// do not leak out into whatever is drawn after it
+    fn draw<F>(&mut self, draw: F)
+    where
+        F: FnOnce(&mut PdfPage),
+    {
+        self.page.ops.push_str("q\n");
+        draw(self.page);
+        self.page.ops.push_str("Q\n");
+    }
+
+    /// Set up the colour, width, join, dash pattern and opacity of lines
+    fn set_stroke(page: &mut PdfPage, style: &LineStyle) -> bool {
+        let (r, g, b) = match parse_colour(&style.get_colour()) {
+            Some(colour) => colour,
+            None => return false,
+        };
+        let join = match style.get_linejoin() {
+            LineJoin::Miter => 0,
+            LineJoin::Round => 1,
+        };
+        let _ = writeln!(
+            page.ops,
+            "{} {} {} RG {} w {} j",
+            r,
+            g,
+            b,
+            style.get_width(),
+            join
+        );
+        let dash = style.get_dash();
+        if !dash.is_empty() {
+            let pattern: Vec<String> = dash.iter().map(|d| d.to_string()).collect();
+            let _ = writeln!(page.ops, "[{}] 0 d", pattern.join(" "));
+        }
+        if style.get_opacity() < 1. {
+            page.set_opacity("CA", style.get_opacity());
+        }
+        true
+    }
+
+    /// Set up the fill and outline of a shape and return the operator that paints it
+    fn set_box(page: &mut PdfPage, style: &BoxStyle) -> Option<&'static str> {
+        // As in SVG, a shape with no fill given is filled in black
+        let fill = match style.get_fill().trim() {
+            "" => Some((0., 0., 0.)),
+            fill => parse_colour(fill),
+        };
+        let op = paint_op(&mut page.ops, fill, parse_colour(&style.get_outline()));
+        if fill.is_some() && style.get_opacity() < 1. {
+            page.set_opacity("ca", style.get_opacity());
+        }
+        op
+    }
+}
+
+impl Surface for PdfSurface<'_> {
+    fn size(&self) -> (f64, f64) {
+        self.size
+    }
+
+    fn line(&mut self, start: (f64, f64), end: (f64, f64), style: &LineStyle) {
+        self.path(&[start, end], style);
+    }
+
+    fn path(&mut self, points: &[(f64, f64)], style: &LineStyle) {
+        if points.is_empty() {
+            return;
+        }
+        self.draw(|page| {
+            if !Self::set_stroke(page, style) {
+                return;
+            }
+            for (i, &(x, y)) in points.iter().enumerate() {
+                let op = if i == 0 { "m" } else { "l" };
+                let _ = writeln!(page.ops, "{} {} {}", x, y, op);
+            }
+            page.ops.push_str("S\n");
+        });
+    }
+
+    fn rect(&mut self, origin: (f64, f64), size: (f64, f64), style: &BoxStyle) {
+        self.draw(|page| {
+            if let Some(op) = Self::set_box(page, style) {
+                let _ = writeln!(
+                    page.ops,
+                    "{} {} {} {} re {}",
+                    origin.0, origin.1, size.0, size.1, op
+                );
+            }
+        });
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], style: &BoxStyle) {
+        if points.is_empty() {
+            return;
+        }
+        self.draw(|page| {
+            if let Some(op) = Self::set_box(page, style) {
+                for (i, &(x, y)) in points.iter().enumerate() {
+                    let command = if i == 0 { "m" } else { "l" };
+                    let _ = writeln!(page.ops, "{} {} {}", x, y, command);
+                }
+                let _ = writeln!(page.ops, "h {}", op);
+            }
+        });
+    }
+
+    fn text(&mut self, position: (f64, f64), text: &str, anchor: TextAnchor, font_size: f64) {
+        self.rotated_text(position, text, anchor, font_size, 0.);
+    }
+
+    fn marker(&mut self, (x, y): (f64, f64), style: &PointStyle) {
+        let colour = match parse_colour(&style.get_colour()) {
+            Some(colour) => colour,
+            None => return,
+        };
+        let r = f64::from(style.get_size());
+        self.draw(|page| {
+            let corners: &[(f64, f64)] = match style.get_marker() {
+                PointMarker::Circle => {
+                    paint_op(&mut page.ops, Some(colour), None);
+                    // Four cubic Bézier curves make a good approximation of a circle
+                    let k = 0.552_284_75 * r;
+                    let _ = writeln!(
+                        page.ops,
+                        "{} {} m {} {} {} {} {} {} c {} {} {} {} {} {} c {} {} {} {} {} {} c {} {} {} {} {} {} c f",
+                        x + r, y,
+                        x + r, y + k, x + k, y + r, x, y + r,
+                        x - k, y + r, x - r, y + k, x - r, y,
+                        x - r, y - k, x - k, y - r, x, y - r,
+                        x + k, y - r, x + r, y - k, x + r, y,
+                    );
+                    return;
+                }
+                PointMarker::Square => &[(-r, -r), (r, -r), (r, r), (-r, r)],
+                PointMarker::Triangle => &[(-r, -r), (r, -r), (0., r)],
+                PointMarker::TriangleDown => &[(-r, r), (r, r), (0., -r)],
+                PointMarker::Diamond => &[(-r, 0.), (0., -r), (r, 0.), (0., r)],
+                PointMarker::Cross | PointMarker::Plus | PointMarker::Star => {
+                    let mut strokes = vec![];
+                    if !matches!(style.get_marker(), PointMarker::Cross) {
+                        strokes.extend([((-r, 0.), (r, 0.)), ((0., -r), (0., r))]);
+                    }
+                    if !matches!(style.get_marker(), PointMarker::Plus) {
+                        strokes.extend([((-r, -r), (r, r)), ((-r, r), (r, -r))]);
+                    }
+                    paint_op(&mut page.ops, None, Some(colour));
+                    for ((x1, y1), (x2, y2)) in strokes {
+                        let _ = writeln!(page.ops, "{} {} m {} {} l", x + x1, y + y1, x + x2, y + y2);
+                    }
+                    page.ops.push_str("S\n");
+                    return;
+                }
+            };
+            paint_op(&mut page.ops, Some(colour), None);
+            for (i, &(dx, dy)) in corners.iter().enumerate() {
+                let command = if i == 0 { "m" } else { "l" };
+                let _ = writeln!(page.ops, "{} {} {}", x + dx, y + dy, command);
+            }
+            page.ops.push_str("h f\n");
+        });
+    }
+
+    fn push_clip(&mut self, origin: (f64, f64), size: (f64, f64)) {
+        let _ = writeln!(
+            self.page.ops,
+            "q\n{} {} {} {} re W n",
+            origin.0, origin.1, size.0, size.1
+        );
+        self.clips += 1;
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clips > 0 {
+            self.page.ops.push_str("Q\n");
+            self.clips -= 1;
+        }
+    }
+
+    fn rotated_text(
+        &mut self,
+        position: (f64, f64),
+        text: &str,
+        anchor: TextAnchor,
+        font_size: f64,
+        angle: f64,
+    ) {
+        if text.trim().is_empty() {
+            return;
+        }
+        // Move back along the baseline from the anchor to where the text starts
+        let width = text_width(text) * font_size;
+        let back = match anchor {
+            TextAnchor::Start => 0.,
+            TextAnchor::Middle => width / 2.,
+            TextAnchor::End => width,
+        };
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (x, y) = (position.0 - back * cos, position.1 - back * sin);
+        let _ = writeln!(
+            self.page.ops,
+            "0 0 0 rg BT /F1 {} Tf {} {} {} {} {} {} Tm ({}) Tj ET",
+            font_size,
+            cos,
+            sin,
+            -sin,
+            cos,
+            x,
+            y,
+            pdf_string(text)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_colour() {
+        assert_eq!(parse_colour("black"), Some((0., 0., 0.)));
+        assert_eq!(parse_colour("#ff0000"), Some((1., 0., 0.)));
+        assert_eq!(parse_colour("#0F0"), Some((0., 1., 0.)));
+        assert_eq!(parse_colour("DarkGrey"), parse_colour("#a9a9a9"));
+        assert_eq!(parse_colour("none"), None);
+        assert_eq!(parse_colour(""), None);
+    }
+
+    #[test]
+    fn test_pdf_string() {
+        assert_eq!(pdf_string("a (b) \\c"), "a \\(b\\) \\\\c");
+        assert_eq!(pdf_string("é"), "\\351");
+    }
+
+    /// Draw onto a face at (10, 20) on a page 100 pixels square, and return the PDF file
+    fn draw_pdf<F>(draw: F) -> String
+    where
+        F: FnOnce(&mut PdfSurface),
+    {
+        let mut page = PdfPage::new(100., 100.);
+        let mut surface = PdfSurface::new(&mut page, (10., 20.), (50., 50.));
+        draw(&mut surface);
+        surface.finish();
+        String::from_utf8(page.into_pdf()).unwrap()
+    }
+
+    #[test]
+    fn test_pdf_page() {
+        let pdf = draw_pdf(|surface| {
+            surface.line((0., 0.), (5., 0.), &LineStyle::new());
+            surface.text((0., 0.), "Hello", TextAnchor::Start, 12.);
+        });
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/BaseFont /Helvetica"));
+        assert!(pdf.contains("/MediaBox [0 0 75 75]"));
+        assert!(pdf.contains("1 0 0 1 10 20 cm"));
+        assert!(pdf.contains("(Hello) Tj"));
+        assert!(pdf.contains("0 0 m\n5 0 l\nS"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+    }
+
+    #[test]
+    fn test_dashed_translucent_line() {
+        let style = LineStyle::new().dash(&[4., 2.]).opacity(0.5);
+        let pdf = draw_pdf(|surface| surface.line((0., 0.), (5., 0.), &style));
+        assert!(pdf.contains("[4 2] 0 d"));
+        assert!(pdf.contains("/GS0 gs"));
+        assert!(pdf.contains("/ExtGState << /GS0 << /CA 0.5 >> >>"));
+    }
+
+    #[test]
+    fn test_clip_path() {
+        let pdf = draw_pdf(|surface| {
+            surface.push_clip((0., 0.), (10., 20.));
+            surface.rect(
+                (5., 5.),
+                (30., 30.),
+                &BoxStyle::new().fill("red").outline("none"),
+            );
+            surface.pop_clip();
+        });
+        // The clip rectangle is used as a clip, not drawn
+        assert!(pdf.contains("q\n0 0 10 20 re W n\n"));
+        assert!(!pdf.contains("0 0 10 20 re f"));
+        assert!(pdf.contains("5 5 30 30 re f"));
+    }
+
+    #[test]
+    fn test_translucent_fill() {
+        let style = BoxStyle::new().fill("red").outline("none").opacity(0.25);
+        let pdf = draw_pdf(|surface| surface.polygon(&[(0., 0.), (10., 0.), (5., 5.)], &style));
+        assert!(pdf.contains("1 0 0 rg\n/GS0 gs"));
+        assert!(pdf.contains("/ExtGState << /GS0 << /ca 0.25 >> >>"));
+    }
+
+    #[test]
+    fn test_rotated_text() {
+        let pdf =
+            draw_pdf(|surface| surface.rotated_text((0., 0.), "Up", TextAnchor::Start, 10., 90.));
+        assert!(pdf.contains("/F1 10 Tf"));
+        assert!(pdf.contains("(Up) Tj"));
+        // The baseline runs up the page
+        let tm = pdf.lines().find(|l| l.contains("(Up) Tj")).unwrap();
+        let numbers: Vec<f64> = tm
+            .split_whitespace()
+            .filter_map(|n| n.parse().ok())
+            .collect();
+        assert!(numbers[4].abs() < 1e-9 && (numbers[5] - 1.).abs() < 1e-9);
+    }
+}
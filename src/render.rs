@@ -1,29 +1,1368 @@
-use crate::scatter;
+/*!
+A backend-agnostic interface for drawing representations.
+
+Representations draw themselves onto a [`Surface`](trait.Surface.html),
+which turns the drawing primitives into a particular output format.
+The SVG and text renderers are both implemented as surfaces
+and a new output format can be added by implementing the trait for it.
+Views draw their axes, grid, legend and title onto the same surface, outside of the face,
+through provided methods which a surface can override to lay them out its own way.
+
+All coordinates are relative to the bottom-left corner of the face of the view,
+with y increasing upwards.
+They are in whatever units the surface works in, e.g. pixels for SVG or character cells for text,
+and `Surface::size` gives the extent of the face in those units.
+Data values are converted into these coordinates with
+[`ContinuousAxis::value_to_face_offset`](../axis/struct.ContinuousAxis.html#method.value_to_face_offset)
+and [`CategoricalAxis::category_to_face_offset`](../axis/struct.CategoricalAxis.html#method.category_to_face_offset).
+*/
+
+use crate::axis;
+use crate::grid::{Grid, GridMode};
+use crate::legend::{Legend, LegendPosition};
 use crate::repr;
-use crate::text_render;
-use crate::svg_render;
+use crate::style::{BoxStyle, LineStyle, PointStyle};
+use crate::utils;
+use crate::utils::PairWise;
+
+/// Where text is placed relative to the position it is drawn at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextAnchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// Which side of the face an axis is drawn along
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisSide {
+    Bottom,
+    Left,
+    Right,
+}
+
+/**
+Something that representations can be drawn onto.
+*/
+pub trait Surface {
+    /// The width and height of the face being drawn on
+    fn size(&self) -> (f64, f64);
+
+    /// Draw a straight line from `start` to `end`
+    fn line(&mut self, start: (f64, f64), end: (f64, f64), style: &LineStyle);
+
+    /// Draw a line joining up a sequence of points
+    fn path(&mut self, points: &[(f64, f64)], style: &LineStyle);
+
+    /// Draw a filled and outlined rectangle with a corner at `origin`
+    fn rect(&mut self, origin: (f64, f64), size: (f64, f64), style: &BoxStyle);
+
+    /// Draw a filled and outlined shape with corners at each of the points
+    ///
+    /// By default this only draws the outline, as a path back round to the first point.
+    fn polygon(&mut self, points: &[(f64, f64)], style: &BoxStyle) {
+        if let Some(&first) = points.first() {
+            let mut outline = points.to_vec();
+            outline.push(first);
+            self.path(&outline, &LineStyle::new().colour(style.get_outline()));
+        }
+    }
+
+    /// Draw a string of text
+    fn text(&mut self, position: (f64, f64), text: &str, anchor: TextAnchor, font_size: f64);
+
+    /// Draw a single point marker centred on `position`
+    fn marker(&mut self, position: (f64, f64), style: &PointStyle);
+
+    /// Restrict all drawing to within a rectangle until the matching `pop_clip`
+    fn push_clip(&mut self, origin: (f64, f64), size: (f64, f64));
+
+    /// Remove the most recently added clip region
+    fn pop_clip(&mut self);
+
+    /// Draw a string of text turned `angle` degrees anticlockwise about `position`
+    ///
+    /// By default the text is drawn level, for surfaces which cannot turn it.
+    fn rotated_text(
+        &mut self,
+        position: (f64, f64),
+        text: &str,
+        anchor: TextAnchor,
+        font_size: f64,
+        _angle: f64,
+    ) {
+        self.text(position, text, anchor, font_size);
+    }
+
+    /// Whether the surface is a grid of characters, one unit to each,
+    /// which some representations are drawn onto differently
+    fn is_text(&self) -> bool {
+        false
+    }
+
+    /// Draw a continuous axis along one side of the face, with its ticks and labels outside it
+    fn axis(&mut self, axis: &axis::ContinuousAxis, side: AxisSide) {
+        draw_axis(axis, side, self);
+    }
+
+    /// Draw a categorical axis along one side of the face, with a tick for each category
+    fn categorical_axis(&mut self, axis: &axis::CategoricalAxis, side: AxisSide) {
+        draw_categorical_axis(axis, side, self);
+    }
+
+    /// Draw a grid across the face.
+    ///
+    /// Vertical lines are only drawn if there is a continuous `x_axis`.
+    fn grid(
+        &mut self,
+        grid: &Grid,
+        x_axis: Option<&axis::ContinuousAxis>,
+        y_axis: &axis::ContinuousAxis,
+    ) {
+        draw_grid(grid, x_axis, y_axis, self);
+    }
+
+    /// Draw the legend entries in a box with its top-left corner at `top_left`
+    fn legend(&mut self, legend: &Legend, entries: &[LegendEntry], top_left: (f64, f64)) {
+        draw_legend(legend, entries, top_left, self);
+    }
+
+    /// Draw the title of a view centred above the face
+    fn title(&mut self, title: &str) {
+        let (face_width, face_height) = self.size();
+        self.text(
+            (face_width / 2., face_height + 10.),
+            title,
+            TextAnchor::Middle,
+            TITLE_FONT_SIZE,
+        );
+    }
+}
+
+/// How a representation should appear in the legend of a view
+#[derive(Debug, Clone, Default)]
+pub struct LegendEntry {
+    pub label: String,
+    pub line_style: Option<LineStyle>,
+    pub point_style: Option<PointStyle>,
+    /// Shown as a filled swatch, for representations drawn as boxes or bars
+    pub box_style: Option<BoxStyle>,
+}
+
+impl LegendEntry {
+    /// An entry shown as a filled swatch in the given style
+    pub fn swatch<T>(label: T, style: &BoxStyle) -> Self
+    where
+        T: Into<String>,
+    {
+        LegendEntry {
+            label: label.into(),
+            box_style: Some(style.clone()),
+            ..LegendEntry::default()
+        }
+    }
+
+    /// An entry shown as a single marker in the given style
+    pub fn marker<T>(label: T, style: &PointStyle) -> Self
+    where
+        T: Into<String>,
+    {
+        LegendEntry {
+            label: label.into(),
+            point_style: Some(style.clone()),
+            ..LegendEntry::default()
+        }
+    }
+}
+
+/// The position of the centre of a category along the face, and the space given to each one
+fn category_position(label: &str, x_axis: &axis::CategoricalAxis, face_width: f64) -> (f64, f64) {
+    let tick_pos = x_axis.category_to_face_offset(label, face_width).unwrap(); // TODO this should raise an error
+    (tick_pos, x_axis.category_width(face_width))
+}
+
+pub(crate) fn draw_face_points(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &PointStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    // Points outside of the axes are left out rather than drawn across them
+    let in_range = |axis: &axis::ContinuousAxis, v: f64| v >= axis.min() && v <= axis.max();
+    for &(x, y) in s
+        .iter()
+        .filter(|&&(x, y)| in_range(x_axis, x) && in_range(y_axis, y))
+    {
+        let x_pos = x_axis.value_to_face_offset(x, face_width);
+        let y_pos = y_axis.value_to_face_offset(y, face_height);
+        surface.marker((x_pos, y_pos), style);
+    }
+}
+
+pub(crate) fn draw_face_line(
+    s: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &LineStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let points: Vec<_> = s
+        .iter()
+        .map(|&(x, y)| {
+            (
+                x_axis.value_to_face_offset(x, face_width),
+                y_axis.value_to_face_offset(y, face_height),
+            )
+        })
+        .collect();
+    surface.path(&points, style);
+}
+
+/// Draw a filled shape with corners at each of the data points
+pub(crate) fn draw_face_polygon(
+    corners: &[(f64, f64)],
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &BoxStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let points: Vec<_> = corners
+        .iter()
+        .map(|&(x, y)| {
+            (
+                x_axis.value_to_face_offset(x, face_width),
+                y_axis.value_to_face_offset(y, face_height),
+            )
+        })
+        .collect();
+    surface.polygon(&points, style);
+}
+
+/// Draw the error bars of a plot, with caps `cap_width` across at each end
+pub(crate) fn draw_face_error_bars(
+    p: &repr::Plot,
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    cap_width: f64,
+) {
+    let (face_width, face_height) = surface.size();
+    let style = &p.error_style;
+    let half_cap = cap_width / 2.;
+    let to_face = |x: f64, y: f64| {
+        (
+            x_axis.value_to_face_offset(x, face_width),
+            y_axis.value_to_face_offset(y, face_height),
+        )
+    };
+
+    if let Some(ref errors) = p.x_errors {
+        for (&(x, y), &(below, above)) in p.data.iter().zip(errors) {
+            let start = to_face(x - below, y);
+            let end = to_face(x + above, y);
+            surface.line(start, end, style);
+            if cap_width > 0. {
+                for &(cap_x, cap_y) in &[start, end] {
+                    surface.line((cap_x, cap_y - half_cap), (cap_x, cap_y + half_cap), style);
+                }
+            }
+        }
+    }
+    if let Some(ref errors) = p.y_errors {
+        for (&(x, y), &(below, above)) in p.data.iter().zip(errors) {
+            let start = to_face(x, y - below);
+            let end = to_face(x, y + above);
+            surface.line(start, end, style);
+            if cap_width > 0. {
+                for &(cap_x, cap_y) in &[start, end] {
+                    surface.line((cap_x - half_cap, cap_y), (cap_x + half_cap, cap_y), style);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn draw_face_bars(
+    h: &repr::Histogram,
+    x_axis: &axis::ContinuousAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &BoxStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let base = y_axis.value_to_face_offset(y_axis.baseline(), face_height);
+
+    for ((&l, &u), &count) in h.bin_bounds.pairwise().zip(h.get_values()) {
+        if !y_axis.get_transform().is_valid_value(count) {
+            // e.g. an empty bin on a logarithmic axis
+            continue;
+        }
+        let l_pos = x_axis.value_to_face_offset(l, face_width);
+        let u_pos = x_axis.value_to_face_offset(u, face_width);
+        let count_pos = y_axis.value_to_face_offset(count, face_height);
+        surface.rect((l_pos, base), (u_pos - l_pos, count_pos - base), style);
+    }
+}
+
+/// The parts of a box plot, in the units of the data
+pub(crate) struct BoxShape {
+    /// The lower quartile, median and upper quartile
+    pub(crate) quartiles: (f64, f64, f64),
+    /// The ends of the lower and upper whiskers
+    pub(crate) whiskers: (f64, f64),
+    /// The bounds of the confidence interval of the median, if the box is notched
+    pub(crate) notch: Option<(f64, f64)>,
+}
+
+pub(crate) fn draw_face_boxplot(
+    shape: &BoxShape,
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &BoxStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, space_per_tick) = category_position(label, x_axis, face_width);
+    let box_width = space_per_tick / 2.;
+    let (left, right) = (tick_pos - (box_width / 2.), tick_pos + (box_width / 2.));
+
+    let (q1, median, q3) = shape.quartiles;
+    let box_start = y_axis.value_to_face_offset(q1, face_height);
+    let box_end = y_axis.value_to_face_offset(q3, face_height);
+    let mid_line = y_axis.value_to_face_offset(median, face_height);
+
+    let line_style = LineStyle::new();
+    match shape.notch {
+        Some((lower, upper)) => {
+            // The notch is pinched in to half the width of the box at the median,
+            // and is kept within the box when the interval is wider than it
+            let notch_start = y_axis.value_to_face_offset(lower.max(q1), face_height);
+            let notch_end = y_axis.value_to_face_offset(upper.min(q3), face_height);
+            let inset = box_width / 4.;
+            surface.polygon(
+                &[
+                    (left, box_start),
+                    (right, box_start),
+                    (right, notch_start),
+                    (right - inset, mid_line),
+                    (right, notch_end),
+                    (right, box_end),
+                    (left, box_end),
+                    (left, notch_end),
+                    (left + inset, mid_line),
+                    (left, notch_start),
+                ],
+                style,
+            );
+            surface.line(
+                (left + inset, mid_line),
+                (right - inset, mid_line),
+                &line_style,
+            );
+        }
+        None => {
+            surface.rect((left, box_start), (box_width, box_end - box_start), style);
+            surface.line((left, mid_line), (right, mid_line), &line_style);
+        }
+    }
+
+    let whisker_bottom = y_axis.value_to_face_offset(shape.whiskers.0, face_height);
+    let whisker_top = y_axis.value_to_face_offset(shape.whiskers.1, face_height);
+    surface.line(
+        (tick_pos, whisker_bottom),
+        (tick_pos, box_start),
+        &line_style,
+    );
+    surface.line((tick_pos, whisker_top), (tick_pos, box_end), &line_style);
+}
+
+/// Draw a density curve either side of the centre of a category, as in a violin plot
+///
+/// Each point of `curve` is a value along with how far the shape reaches either side of the centre
+/// at that value, as a fraction of the space given to the category.
+/// The left and right sides are scaled by `sides`, so that a side can be left out.
+pub(crate) fn draw_face_violin(
+    curve: &[(f64, f64)],
+    sides: (f64, f64),
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &BoxStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, space_per_tick) = category_position(label, x_axis, face_width);
+    let position = |&(v, reach): &(f64, f64), side: f64| {
+        (
+            tick_pos + side * reach * space_per_tick,
+            y_axis.value_to_face_offset(v, face_height),
+        )
+    };
+    // Up the right-hand side and back down the left
+    let corners: Vec<_> = curve
+        .iter()
+        .map(|point| position(point, sides.1))
+        .chain(curve.iter().rev().map(|point| position(point, -sides.0)))
+        .collect();
+    surface.polygon(&corners, style);
+}
+
+/// Draw a line across a category at a value, reaching `extent` either side of the centre
+/// as fractions of the space given to the category
+pub(crate) fn draw_face_category_line(
+    value: f64,
+    extent: (f64, f64),
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &LineStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, space_per_tick) = category_position(label, x_axis, face_width);
+    let y = y_axis.value_to_face_offset(value, face_height);
+    surface.line(
+        (tick_pos - extent.0 * space_per_tick, y),
+        (tick_pos + extent.1 * space_per_tick, y),
+        style,
+    );
+}
+
+/// Draw a marker for each value, shifted across the category by the matching offset
+/// as a fraction of the space given to the category
+pub(crate) fn draw_face_strip(
+    values: &[f64],
+    offsets: &[f64],
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &PointStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, space_per_tick) = category_position(label, x_axis, face_width);
+    for (&v, &offset) in values.iter().zip(offsets) {
+        let position = (
+            tick_pos + offset * space_per_tick,
+            y_axis.value_to_face_offset(v, face_height),
+        );
+        surface.marker(position, style);
+    }
+}
+
+/**
+Draw a marker for each value, spread across the category so that none of them overlap
+
+Taking the values in order, each marker is put as close to the centre of the category as it can be
+without touching those already placed.
+Markers are spaced by their size, or by one character on a text surface.
+If the swarm is then wider than the category, it is squeezed to fit,
+so that the markers overlap rather than spill into the next category.
+Values which are not finite are left out.
+*/
+pub(crate) fn draw_face_swarm(
+    values: &[f64],
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &PointStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, space_per_tick) = category_position(label, x_axis, face_width);
+    let is_text = surface.is_text();
+    let spacing = if is_text {
+        1.
+    } else {
+        2. * f64::from(style.get_size()) + 1.
+    };
+
+    let mut heights: Vec<f64> = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .map(|&v| y_axis.value_to_face_offset(v, face_height))
+        // On a text surface only markers in the same row can touch
+        .map(|y| if is_text { y.round() } else { y })
+        .collect();
+    heights.sort_by(f64::total_cmp);
+
+    let mut placed: Vec<(f64, f64)> = vec![];
+    for y in heights {
+        let neighbours: Vec<(f64, f64)> = placed
+            .iter()
+            .filter(|&&(_, other_y)| (y - other_y).abs() < spacing)
+            .cloned()
+            .collect();
+        // The centre, or just touching either side of one of the neighbours
+        let mut candidates = vec![0.];
+        for &(other_x, other_y) in &neighbours {
+            let reach = (spacing.powi(2) - (y - other_y).powi(2)).sqrt();
+            candidates.push(other_x - reach);
+            candidates.push(other_x + reach);
+        }
+        let x = candidates
+            .into_iter()
+            .filter(|&x| {
+                neighbours.iter().all(|&(other_x, other_y)| {
+                    (x - other_x).powi(2) + (y - other_y).powi(2) >= spacing.powi(2) - 1e-6
+                })
+            })
+            .fold(
+                f64::INFINITY,
+                |best, x| {
+                    if x.abs() < best.abs() {
+                        x
+                    } else {
+                        best
+                    }
+                },
+            );
+        placed.push((x, y));
+    }
+
+    // Keep the swarm within its half of the space either side of the tick
+    let half_width = (space_per_tick / 2. - spacing / 2.).max(0.);
+    let widest = placed
+        .iter()
+        .fold(0f64, |widest, &(x, _)| widest.max(x.abs()));
+    let squeeze = if widest > half_width {
+        half_width / widest
+    } else {
+        1.
+    };
+    for (x, y) in placed {
+        surface.marker((tick_pos + x * squeeze, y), style);
+    }
+}
+
+/// Draw a marker for each value, centred on the category
+pub(crate) fn draw_face_category_points(
+    values: &[f64],
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &PointStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, _) = category_position(label, x_axis, face_width);
+    for &v in values {
+        surface.marker(
+            (tick_pos, y_axis.value_to_face_offset(v, face_height)),
+            style,
+        );
+    }
+}
+
+pub(crate) fn draw_face_barchart(
+    d: f64,
+    label: &str,
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &BoxStyle,
+) {
+    draw_face_bar(
+        label,
+        (y_axis.baseline(), d),
+        (-0.25, 0.5),
+        x_axis,
+        y_axis,
+        surface,
+        style,
+    );
+}
+
+/// Draw a bar from `start` to `end` in the y-axis
+///
+/// The left edge and width of the bar across the category are given by `across`,
+/// as fractions of the space given to each category, relative to its tick.
+pub(crate) fn draw_face_bar(
+    label: &str,
+    (start, end): (f64, f64),
+    across: (f64, f64),
+    x_axis: &axis::CategoricalAxis,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut dyn Surface,
+    style: &BoxStyle,
+) {
+    let (face_width, face_height) = surface.size();
+    let (tick_pos, space_per_tick) = category_position(label, x_axis, face_width);
+
+    let box_start = y_axis.value_to_face_offset(start, face_height);
+    let box_end = y_axis.value_to_face_offset(end, face_height);
+
+    surface.rect(
+        (tick_pos + across.0 * space_per_tick, box_start),
+        (across.1 * space_per_tick, box_end - box_start),
+        style,
+    );
+}
+
+/// Glyph widths of Helvetica for the printable ASCII characters, in thousandths of an em
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' to '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // '0' to '9'
+    278, 278, 584, 584, 584, 556, 1015, // ':' to '@'
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // 'A' to 'M'
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // 'N' to 'Z'
+    278, 278, 278, 469, 556, 333, // '[' to '`'
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // 'a' to 'm'
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // 'n' to 'z'
+    334, 260, 334, 584, // '{' to '~'
+];
+
+/// The width of a string set in Helvetica, the font of PDF output, at a font size of 1
+pub(crate) fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| {
+            let width = match c as usize {
+                i @ 32..=126 => HELVETICA_WIDTHS[i - 32],
+                _ => 556,
+            };
+            f64::from(width) / 1000.
+        })
+        .sum()
+}
+
+/// The font size of the title of a view
+const TITLE_FONT_SIZE: f64 = 14.;
+
+/// The font size of the tick labels and axis labels
+pub(crate) const AXIS_FONT_SIZE: f64 = 12.;
+
+/// The font size of the category labels along a categorical axis
+pub(crate) const CATEGORY_FONT_SIZE: f64 = 9.;
+
+/// How far below the middle of a line of text its baseline is, as a fraction of the font size
+const HALF_CAP_HEIGHT: f64 = 0.35;
+
+/// How much wider than Helvetica the font that text is shown in may be.
+/// SVG is shown in whichever sans-serif font the viewer has, and many, such as DejaVu Sans, are wider,
+/// so this much room is left when laying out text to stop it running into what is beside it.
+const FONT_WIDTH_ALLOWANCE: f64 = 1.15;
+
+/// The room to leave for some text at the given font size when laying out a view
+fn layout_text_width(text: &str, font_size: f64) -> f64 {
+    FONT_WIDTH_ALLOWANCE * text_width(text) * font_size
+}
+
+/// The room to leave for some text in the font used on the axes
+fn axis_text_width(text: &str) -> f64 {
+    layout_text_width(text, AXIS_FONT_SIZE)
+}
+
+/// How far out from a vertical axis line its tick labels reach
+fn vertical_tick_labels_width(a: &axis::ContinuousAxis) -> f64 {
+    let widest = a
+        .ticks()
+        .iter()
+        .map(|&t| axis_text_width(&a.tick_label(t)))
+        .fold(0., f64::max);
+    15. + widest
+}
+
+/// The space needed beside the face for a vertical axis, with its tick labels and axis label
+pub(crate) fn vertical_axis_width(a: &axis::ContinuousAxis) -> f64 {
+    let ticks = vertical_tick_labels_width(a);
+    if a.get_label().is_empty() {
+        ticks + 4.
+    } else {
+        // The rotated label sits a little beyond the tick labels
+        ticks + 6. + 0.75 * AXIS_FONT_SIZE + 4.
+    }
+}
 
-pub trait Render {
-    fn to_svg(&self) -> svg_render::SVG;
-    fn to_text(&self) -> text_render::Text;
+/// How far out from a vertical categorical axis line its category labels reach
+fn vertical_category_labels_width(a: &axis::CategoricalAxis) -> f64 {
+    let widest = a
+        .ticks()
+        .iter()
+        .map(|tick| layout_text_width(tick, CATEGORY_FONT_SIZE))
+        .fold(0., f64::max);
+    15. + widest
 }
 
-impl Render for scatter::Scatter {
-    fn to_text(&self) -> text_render::Text {
-        text_render::Text {data: text_render::draw_scatter(self)}
+/// The space needed beside the face for a vertical categorical axis, with its labels
+pub(crate) fn vertical_categorical_axis_width(a: &axis::CategoricalAxis) -> f64 {
+    let ticks = vertical_category_labels_width(a);
+    if a.get_label().is_empty() {
+        ticks + 4.
+    } else {
+        ticks + 6. + 0.75 * AXIS_FONT_SIZE + 4.
     }
+}
 
-    fn to_svg(&self) -> svg_render::SVG {
-        svg_render::draw_scatter(self)
+/// The space needed below the face for a horizontal axis, with its tick labels and axis label
+pub(crate) fn horizontal_axis_height(label: &str) -> f64 {
+    if label.is_empty() {
+        26.
+    } else {
+        36.
     }
 }
 
-impl Render for repr::Histogram {
-    fn to_text(&self) -> text_render::Text {
-        text_render::Text {data: text_render::draw_histogram(self)}
+/// How far the first and last tick labels of a horizontal axis reach beyond the ends of the face
+pub(crate) fn horizontal_axis_overhang(a: &axis::ContinuousAxis, face_width: f64) -> (f64, f64) {
+    let mut overhang = (0f64, 0f64);
+    for &tick in a.ticks() {
+        let position = a.value_to_face_offset(tick, face_width);
+        let half_width = axis_text_width(&a.tick_label(tick)) / 2.;
+        overhang.0 = overhang.0.max(half_width - position);
+        overhang.1 = overhang.1.max(position + half_width - face_width);
     }
+    overhang
+}
+
+/// Where a vertical axis on the given side of the face is drawn,
+/// which way is outwards from the face, and how its tick labels are anchored
+fn vertical_axis_placement(side: AxisSide, face_width: f64) -> (f64, f64, TextAnchor) {
+    match side {
+        AxisSide::Right => (face_width, 1., TextAnchor::Start),
+        _ => (0., -1., TextAnchor::End),
+    }
+}
+
+/// Draw a continuous axis along one side of the face, with its ticks and labels outside it
+pub(crate) fn draw_axis<S: Surface + ?Sized>(
+    a: &axis::ContinuousAxis,
+    side: AxisSide,
+    surface: &mut S,
+) {
+    let (face_width, face_height) = surface.size();
+    let style = LineStyle::new();
+
+    if side == AxisSide::Bottom {
+        for &tick in a.ticks() {
+            let x = a.value_to_face_offset(tick, face_width);
+            surface.line((x, 0.), (x, -10.), &style);
+            surface.text(
+                (x, -20.),
+                &a.tick_label(tick),
+                TextAnchor::Middle,
+                AXIS_FONT_SIZE,
+            );
+        }
+        for &tick in a.get_minor_ticks() {
+            let x = a.value_to_face_offset(tick, face_width);
+            surface.line((x, 0.), (x, -5.), &style);
+        }
+        surface.line((0., 0.), (face_width, 0.), &style);
+        if !a.get_label().is_empty() {
+            surface.text(
+                (face_width / 2., -30.),
+                a.get_label(),
+                TextAnchor::Middle,
+                AXIS_FONT_SIZE,
+            );
+        }
+        return;
+    }
+
+    let (x, out, anchor) = vertical_axis_placement(side, face_width);
+    for &tick in a.ticks() {
+        let y = a.value_to_face_offset(tick, face_height);
+        surface.line((x, y), (x + 10. * out, y), &style);
+        surface.text(
+            (x + 15. * out, y - HALF_CAP_HEIGHT * AXIS_FONT_SIZE),
+            &a.tick_label(tick),
+            anchor,
+            AXIS_FONT_SIZE,
+        );
+    }
+    for &tick in a.get_minor_ticks() {
+        let y = a.value_to_face_offset(tick, face_height);
+        surface.line((x, y), (x + 5. * out, y), &style);
+    }
+    surface.line((x, 0.), (x, face_height), &style);
+    if !a.get_label().is_empty() {
+        // The label reads up the left-hand side and down the right,
+        // with its baseline just beyond the widest tick label
+        surface.rotated_text(
+            (
+                x + out * (vertical_tick_labels_width(a) + 6.),
+                face_height / 2.,
+            ),
+            a.get_label(),
+            TextAnchor::Middle,
+            AXIS_FONT_SIZE,
+            -90. * out,
+        );
+    }
+}
+
+/// Draw a categorical axis along one side of the face, with a tick for each category.
+///
+/// Up the side of the face, the first category is at the top.
+pub(crate) fn draw_categorical_axis<S: Surface + ?Sized>(
+    a: &axis::CategoricalAxis,
+    side: AxisSide,
+    surface: &mut S,
+) {
+    let (face_width, face_height) = surface.size();
+    let style = LineStyle::new();
+
+    if side == AxisSide::Bottom {
+        let space_per_tick = a.category_width(face_width);
+        for (i, tick) in a.ticks().iter().enumerate() {
+            let x = (i as f64 + 0.5) * space_per_tick;
+            surface.line((x, 0.), (x, -10.), &style);
+            // Tilted a little, so that long neighbouring labels are less likely to run together
+            surface.rotated_text(
+                (x, -20.),
+                tick,
+                TextAnchor::Middle,
+                CATEGORY_FONT_SIZE,
+                -10.,
+            );
+        }
+        surface.line((0., 0.), (face_width, 0.), &style);
+        if !a.get_label().is_empty() {
+            surface.text(
+                (face_width / 2., -30.),
+                a.get_label(),
+                TextAnchor::Middle,
+                CATEGORY_FONT_SIZE,
+            );
+        }
+        return;
+    }
+
+    let (x, out, anchor) = vertical_axis_placement(side, face_width);
+    let space_per_tick = a.category_width(face_height);
+    for (i, tick) in a.ticks().iter().enumerate() {
+        let y = face_height - (i as f64 + 0.5) * space_per_tick;
+        surface.line((x, y), (x + 10. * out, y), &style);
+        surface.text(
+            (x + 15. * out, y - HALF_CAP_HEIGHT * CATEGORY_FONT_SIZE),
+            tick,
+            anchor,
+            CATEGORY_FONT_SIZE,
+        );
+    }
+    surface.line((x, 0.), (x, face_height), &style);
+    if !a.get_label().is_empty() {
+        surface.rotated_text(
+            (
+                x + out * (vertical_category_labels_width(a) + 6.),
+                face_height / 2.,
+            ),
+            a.get_label(),
+            TextAnchor::Middle,
+            AXIS_FONT_SIZE,
+            -90. * out,
+        );
+    }
+}
+
+/// Draw the characters of a picture made of text, laid out as rows from the top of the face down.
+///
+/// Spaces are left undrawn, so that whatever is underneath shows through.
+pub(crate) fn draw_face_characters<S: Surface + ?Sized>(rows: &str, surface: &mut S) {
+    let (_, face_height) = surface.size();
+    for (row, line) in rows.lines().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            if c != ' ' {
+                surface.text(
+                    ((column + 1) as f64, face_height - row as f64),
+                    &c.to_string(),
+                    TextAnchor::Start,
+                    AXIS_FONT_SIZE,
+                );
+            }
+        }
+    }
+}
+
+/// The font size of the legend entries
+const LEGEND_FONT_SIZE: f64 = 9.0;
+/// The font size of the title of the legend
+const LEGEND_TITLE_FONT_SIZE: f64 = 10.0;
+/// The height of each row of the legend
+const LEGEND_ROW_HEIGHT: f64 = 18.0;
+/// The space given to the key of each entry, to the left of its label
+const LEGEND_KEY_WIDTH: f64 = 23.0;
+/// The space between the edge of the legend and its contents
+const LEGEND_PADDING: f64 = 6.0;
+/// The space between neighbouring columns of the legend
+const LEGEND_COLUMN_GAP: f64 = 12.0;
+
+/// The entries in each column of the legend, filling each column in turn.
+/// The entries are spread over as many of the columns as there are entries,
+/// with the earlier columns taking one more entry when they do not divide evenly.
+fn legend_columns<'a>(legend: &Legend, entries: &'a [LegendEntry]) -> Vec<&'a [LegendEntry]> {
+    let columns = legend.get_columns().min(entries.len());
+    let mut rest = entries;
+    (0..columns)
+        .map(|column| {
+            let rows = rest.len().div_ceil(columns - column);
+            let (entries, remaining) = rest.split_at(rows);
+            rest = remaining;
+            entries
+        })
+        .collect()
+}
+
+/// The width of each column of the legend
+fn legend_column_widths(legend: &Legend, entries: &[LegendEntry]) -> Vec<f64> {
+    legend_columns(legend, entries)
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .map(|entry| LEGEND_KEY_WIDTH + layout_text_width(&entry.label, LEGEND_FONT_SIZE))
+                .fold(0., f64::max)
+        })
+        .collect()
+}
+
+/// The width and height of the legend, including its title and padding
+pub(crate) fn legend_size(legend: &Legend, entries: &[LegendEntry]) -> (f64, f64) {
+    let column_widths = legend_column_widths(legend, entries);
+    let columns_width = column_widths.iter().sum::<f64>()
+        + LEGEND_COLUMN_GAP * column_widths.len().saturating_sub(1) as f64;
+    let rows = legend_columns(legend, entries)
+        .first()
+        .map_or(0, |column| column.len());
+
+    let (title_width, title_height) = match legend.get_title() {
+        Some(title) => (
+            layout_text_width(title, LEGEND_TITLE_FONT_SIZE),
+            LEGEND_ROW_HEIGHT,
+        ),
+        None => (0., 0.),
+    };
+
+    (
+        columns_width.max(title_width) + 2. * LEGEND_PADDING,
+        title_height + rows as f64 * LEGEND_ROW_HEIGHT + 2. * LEGEND_PADDING,
+    )
+}
+
+/// The top-left corner of a legend of the given size placed in one of the corners of the face
+///
+/// `position` should be one of the corner positions, anything else is placed at the top right.
+pub(crate) fn legend_corner(
+    position: LegendPosition,
+    size: (f64, f64),
+    face_size: (f64, f64),
+) -> (f64, f64) {
+    const INSET: f64 = 10.;
+    let (width, height) = size;
+    let (face_width, face_height) = face_size;
+    let left = INSET;
+    let right = face_width - INSET - width;
+    let top = face_height - INSET;
+    let bottom = INSET + height;
+    match position {
+        LegendPosition::TopLeft => (left, top),
+        LegendPosition::BottomLeft => (left, bottom),
+        LegendPosition::BottomRight => (right, bottom),
+        _ => (right, top),
+    }
+}
+
+/// Choose the corner of the face where a legend of the given size covers the fewest of `points`
+///
+/// Ties are broken in the order top right, top left, bottom left, bottom right.
+pub(crate) fn best_legend_corner(
+    size: (f64, f64),
+    face_size: (f64, f64),
+    points: &[(f64, f64)],
+) -> LegendPosition {
+    let candidates = [
+        LegendPosition::TopRight,
+        LegendPosition::TopLeft,
+        LegendPosition::BottomLeft,
+        LegendPosition::BottomRight,
+    ];
+    // Allow for markers reaching a little beyond the points they are centred on
+    const MARKER_RADIUS: f64 = 5.;
+    let covered = |position: LegendPosition| {
+        let (left, top) = legend_corner(position, size, face_size);
+        let (left, right) = (left - MARKER_RADIUS, left + size.0 + MARKER_RADIUS);
+        let (bottom, top) = (top - size.1 - MARKER_RADIUS, top + MARKER_RADIUS);
+        points
+            .iter()
+            .filter(|&&(x, y)| x >= left && x <= right && y >= bottom && y <= top)
+            .count()
+    };
+    candidates
+        .iter()
+        .cloned()
+        .min_by_key(|&position| covered(position))
+        .unwrap_or(LegendPosition::TopRight)
+}
+
+/// Draw the legend entries in a box with its top-left corner at `top_left`
+pub(crate) fn draw_legend<S: Surface + ?Sized>(
+    legend: &Legend,
+    entries: &[LegendEntry],
+    top_left: (f64, f64),
+    surface: &mut S,
+) {
+    let (width, height) = legend_size(legend, entries);
+    let (left, top) = top_left;
+
+    if let Some(background) = legend.get_background() {
+        surface.rect(
+            (left, top - height),
+            (width, height),
+            &BoxStyle::new().fill(background).outline("none"),
+        );
+    }
+    if let Some(frame) = legend.get_frame() {
+        surface.path(
+            &[
+                (left, top),
+                (left + width, top),
+                (left + width, top - height),
+                (left, top - height),
+                (left, top),
+            ],
+            frame,
+        );
+    }
+
+    // Text is drawn on its baseline, a little below the middle of its row
+    let baseline = |row_top: f64| row_top - LEGEND_ROW_HEIGHT / 2. - 3.;
+
+    let mut row_top = top - LEGEND_PADDING;
+    if let Some(title) = legend.get_title() {
+        surface.text(
+            (left + LEGEND_PADDING, baseline(row_top)),
+            title,
+            TextAnchor::Start,
+            LEGEND_TITLE_FONT_SIZE,
+        );
+        row_top -= LEGEND_ROW_HEIGHT;
+    }
+
+    let mut x = left + LEGEND_PADDING;
+    for (column, column_width) in legend_columns(legend, entries)
+        .iter()
+        .zip(legend_column_widths(legend, entries))
+    {
+        for (row, entry) in column.iter().enumerate() {
+            let entry_top = row_top - row as f64 * LEGEND_ROW_HEIGHT;
+            let text_x = x + LEGEND_KEY_WIDTH;
+            surface.text(
+                (text_x, baseline(entry_top)),
+                &entry.label,
+                TextAnchor::Start,
+                LEGEND_FONT_SIZE,
+            );
+
+            // The key sits to the left of the text, level with the middle of it
+            let key_y = baseline(entry_top) + LEGEND_FONT_SIZE / 2. - 2.;
+            if let Some(ref style) = entry.box_style {
+                surface.rect((text_x - 20., key_y - 4.5), (14., 9.), style);
+            }
+            if let Some(ref style) = entry.line_style {
+                surface.line((text_x - 23., key_y), (text_x - 3., key_y), style);
+            }
+            if let Some(ref style) = entry.point_style {
+                surface.marker((text_x - 13., key_y), style);
+            }
+        }
+        x += column_width + LEGEND_COLUMN_GAP;
+    }
+}
+
+/**
+A surface which draws nothing but records the points where things would have been drawn.
+
+Lines and the areas of rectangles are sampled at regular intervals,
+so that the points give an idea of which parts of the face are covered.
+*/
+pub(crate) struct FootprintSurface {
+    size: (f64, f64),
+    pub(crate) points: Vec<(f64, f64)>,
+}
+
+impl FootprintSurface {
+    /// The number of steps each line or side of a rectangle is sampled in
+    const SAMPLES: usize = 10;
+
+    pub(crate) fn new(face_width: f64, face_height: f64) -> Self {
+        FootprintSurface {
+            size: (face_width, face_height),
+            points: vec![],
+        }
+    }
+}
+
+impl Surface for FootprintSurface {
+    fn size(&self) -> (f64, f64) {
+        self.size
+    }
+
+    fn line(&mut self, start: (f64, f64), end: (f64, f64), _style: &LineStyle) {
+        let n = Self::SAMPLES;
+        self.points.extend((0..=n).map(|i| {
+            let t = i as f64 / n as f64;
+            (
+                start.0 + t * (end.0 - start.0),
+                start.1 + t * (end.1 - start.1),
+            )
+        }));
+    }
+
+    fn path(&mut self, points: &[(f64, f64)], style: &LineStyle) {
+        for (&start, &end) in points.pairwise() {
+            self.line(start, end, style);
+        }
+    }
+
+    fn rect(&mut self, origin: (f64, f64), size: (f64, f64), _style: &BoxStyle) {
+        let n = Self::SAMPLES;
+        for i in 0..=n {
+            for j in 0..=n {
+                self.points.push((
+                    origin.0 + size.0 * i as f64 / n as f64,
+                    origin.1 + size.1 * j as f64 / n as f64,
+                ));
+            }
+        }
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], _style: &BoxStyle) {
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return,
+        };
+        let edge_style = LineStyle::new();
+        self.path(points, &edge_style);
+        self.line(last, first, &edge_style);
+
+        // Sample across the bounding box, keeping the points inside the shape
+        let (min_x, max_x, min_y, max_y) = points.iter().fold(
+            (
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(min_x, max_x, min_y, max_y), &(x, y)| {
+                (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+            },
+        );
+        let n = Self::SAMPLES;
+        for i in 0..=n {
+            for j in 0..=n {
+                let x = min_x + (max_x - min_x) * i as f64 / n as f64;
+                let y = min_y + (max_y - min_y) * j as f64 / n as f64;
+                if utils::point_in_polygon((x, y), points) {
+                    self.points.push((x, y));
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, position: (f64, f64), _text: &str, _anchor: TextAnchor, _font_size: f64) {
+        self.points.push(position);
+    }
+
+    fn marker(&mut self, position: (f64, f64), _style: &PointStyle) {
+        self.points.push(position);
+    }
+
+    fn push_clip(&mut self, _origin: (f64, f64), _size: (f64, f64)) {}
+
+    fn pop_clip(&mut self) {}
+}
+
+/**
+A surface which turns whatever is drawn onto it a quarter turn before passing it on to another.
+
+Representations drawn onto it with categories along the x-axis end up with the categories
+running down the face from the top, and the values increasing to the right.
+*/
+pub(crate) struct HorizontalSurface<'a> {
+    inner: &'a mut dyn Surface,
+}
+
+impl<'a> HorizontalSurface<'a> {
+    pub(crate) fn new(inner: &'a mut dyn Surface) -> Self {
+        HorizontalSurface { inner }
+    }
+
+    /// Where a point on this surface ends up on the surface underneath
+    fn turn(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (y, self.inner.size().1 - x)
+    }
+
+    /// Where a rectangle ends up on the surface underneath, as its lower-left corner and its size
+    fn turn_rect(&self, origin: (f64, f64), size: (f64, f64)) -> ((f64, f64), (f64, f64)) {
+        let (x1, y1) = self.turn(origin);
+        let (x2, y2) = self.turn((origin.0 + size.0, origin.1 + size.1));
+        ((x1.min(x2), y1.min(y2)), ((x2 - x1).abs(), (y2 - y1).abs()))
+    }
+}
+
+impl<'a> Surface for HorizontalSurface<'a> {
+    fn size(&self) -> (f64, f64) {
+        let (width, height) = self.inner.size();
+        (height, width)
+    }
+
+    fn line(&mut self, start: (f64, f64), end: (f64, f64), style: &LineStyle) {
+        let (start, end) = (self.turn(start), self.turn(end));
+        self.inner.line(start, end, style);
+    }
+
+    fn path(&mut self, points: &[(f64, f64)], style: &LineStyle) {
+        let points: Vec<_> = points.iter().map(|&p| self.turn(p)).collect();
+        self.inner.path(&points, style);
+    }
+
+    fn rect(&mut self, origin: (f64, f64), size: (f64, f64), style: &BoxStyle) {
+        let (origin, size) = self.turn_rect(origin, size);
+        self.inner.rect(origin, size, style);
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], style: &BoxStyle) {
+        let points: Vec<_> = points.iter().map(|&p| self.turn(p)).collect();
+        self.inner.polygon(&points, style);
+    }
+
+    fn text(&mut self, position: (f64, f64), text: &str, anchor: TextAnchor, font_size: f64) {
+        let position = self.turn(position);
+        self.inner.text(position, text, anchor, font_size);
+    }
+
+    fn marker(&mut self, position: (f64, f64), style: &PointStyle) {
+        let position = self.turn(position);
+        self.inner.marker(position, style);
+    }
+
+    fn push_clip(&mut self, origin: (f64, f64), size: (f64, f64)) {
+        let (origin, size) = self.turn_rect(origin, size);
+        self.inner.push_clip(origin, size);
+    }
+
+    fn pop_clip(&mut self) {
+        self.inner.pop_clip();
+    }
+
+    fn rotated_text(
+        &mut self,
+        position: (f64, f64),
+        text: &str,
+        anchor: TextAnchor,
+        font_size: f64,
+        angle: f64,
+    ) {
+        let position = self.turn(position);
+        self.inner
+            .rotated_text(position, text, anchor, font_size, angle);
+    }
+
+    fn is_text(&self) -> bool {
+        self.inner.is_text()
+    }
+}
+
+/// A line of a grid, running across the whole face
+pub(crate) struct GridLine {
+    /// Whether the line runs up the face, rather than across it
+    pub(crate) vertical: bool,
+    /// How far along the face the line is
+    pub(crate) offset: f64,
+    /// Whether this is a major grid line, rather than one at a minor tick
+    pub(crate) major: bool,
+}
+
+/// Work out where the lines of a grid go, with the minor lines before the major ones.
+///
+/// Vertical lines are only drawn if there is a continuous `x_axis`.
+pub(crate) fn grid_lines(
+    grid: &Grid,
+    x_axis: Option<&axis::ContinuousAxis>,
+    y_axis: &axis::ContinuousAxis,
+    face_width: f64,
+    face_height: f64,
+) -> Vec<GridLine> {
+    let mut lines = vec![];
+
+    let mut add_axis_lines = |axis: &axis::ContinuousAxis, vertical: bool, face_size: f64| {
+        if grid.mode == GridMode::AllTicks {
+            lines.extend(axis.get_minor_ticks().iter().map(|&tick| GridLine {
+                vertical,
+                offset: axis.value_to_face_offset(tick, face_size),
+                major: false,
+            }));
+        }
+        lines.extend(axis.ticks().iter().map(|&tick| GridLine {
+            vertical,
+            offset: axis.value_to_face_offset(tick, face_size),
+            major: true,
+        }));
+    };
+
+    match grid.mode {
+        GridMode::Even => {
+            let mut add_even_lines = |count: u32, vertical: bool, face_size: f64| {
+                let step = face_size / f64::from(count);
+                lines.extend((0..=count).map(|i| GridLine {
+                    vertical,
+                    offset: f64::from(i) * step,
+                    major: true,
+                }));
+            };
+            add_even_lines(grid.ny, false, face_height);
+            if x_axis.is_some() {
+                add_even_lines(grid.nx, true, face_width);
+            }
+        }
+        GridMode::MajorTicks | GridMode::AllTicks => {
+            add_axis_lines(y_axis, false, face_height);
+            if let Some(x_axis) = x_axis {
+                add_axis_lines(x_axis, true, face_width);
+            }
+        }
+    }
+    lines
+}
+
+/// Draw a grid across the face.
+///
+/// Vertical lines are only drawn if there is a continuous `x_axis`.
+pub(crate) fn draw_grid<S: Surface + ?Sized>(
+    grid: &Grid,
+    x_axis: Option<&axis::ContinuousAxis>,
+    y_axis: &axis::ContinuousAxis,
+    surface: &mut S,
+) {
+    let (face_width, face_height) = surface.size();
+    let major_style = grid.get_major_style();
+    for line in grid_lines(grid, x_axis, y_axis, face_width, face_height) {
+        let style = if line.major {
+            &major_style
+        } else {
+            &grid.minor_style
+        };
+        if line.vertical {
+            surface.line((line.offset, 0.), (line.offset, face_height), style);
+        } else {
+            surface.line((0., line.offset), (face_width, line.offset), style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn to_svg(&self) -> svg_render::SVG {
-        svg_render::draw_histogram(self)
+    #[test]
+    fn test_text_width() {
+        assert_eq!(text_width(""), 0.);
+        assert!((text_width("10") - 1.112).abs() < 1e-9);
     }
 }
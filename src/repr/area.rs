@@ -0,0 +1,187 @@
+/*!
+
+Filled areas, under a curve or between two curves
+
+# Examples
+
+```
+# use plotlib::repr::Area;
+# use plotlib::style::{BoxStyle, LineStyle};
+# use plotlib::view::ContinuousView;
+// The area under a curve, down to zero
+let a = Area::new(vec![(0., 1.), (1., 3.), (2., 2.)]);
+
+// A band around a mean, with the mean drawn as the upper and lower edges
+let band = Area::between(
+    vec![(0., 1.5), (1., 3.2), (2., 2.6)],
+    vec![(0., 0.5), (1., 2.8), (2., 1.4)],
+)
+.style(&BoxStyle::new().fill("steelblue").opacity(0.3))
+.edge_style(LineStyle::new().colour("steelblue"));
+
+let v = ContinuousView::new().add(a).add(band);
+```
+
+Stacked areas are made by filling between running totals:
+
+```
+# use plotlib::repr::Area;
+let first = vec![(0., 1.), (1., 2.), (2., 1.5)];
+let second = vec![(0., 2.), (1., 2.5), (2., 3.)];
+let total: Vec<_> = first
+    .iter()
+    .zip(&second)
+    .map(|(&(x, a), &(_, b))| (x, a + b))
+    .collect();
+let bottom = Area::new(first.clone());
+let top = Area::between(total, first);
+```
+*/
+
+use std::f64;
+
+use crate::axis;
+use crate::render::{self, LegendEntry, Surface};
+use crate::repr::ContinuousRepresentation;
+use crate::style::{BoxStyle, LineStyle};
+
+/// What the lower edge of an `Area` follows
+#[derive(Debug, Clone)]
+pub enum AreaBase {
+    /// A constant y value
+    Constant(f64),
+    /// A second curve
+    Curve(Vec<(f64, f64)>),
+}
+
+/// An area filled between a curve and either a constant baseline or a second curve
+#[derive(Debug, Clone)]
+pub struct Area {
+    pub data: Vec<(f64, f64)>,
+    pub base: AreaBase,
+    style: BoxStyle,
+    /// None if the edges should not be outlined
+    edge_style: Option<LineStyle>,
+    legend: Option<String>,
+}
+
+impl Area {
+    /// Fill the area between a curve and zero
+    pub fn new(data: Vec<(f64, f64)>) -> Self {
+        Area {
+            data,
+            base: AreaBase::Constant(0.),
+            style: BoxStyle::new().fill("lightgrey"),
+            edge_style: None,
+            legend: None,
+        }
+    }
+
+    /// Fill the area between two curves
+    pub fn between(upper: Vec<(f64, f64)>, lower: Vec<(f64, f64)>) -> Self {
+        Area {
+            base: AreaBase::Curve(lower),
+            ..Area::new(upper)
+        }
+    }
+
+    /// Fill down to a constant y value rather than to zero
+    pub fn baseline(mut self, y: f64) -> Self {
+        self.base = AreaBase::Constant(y);
+        self
+    }
+
+    /// Set the fill colour and opacity, which default to solid light grey
+    pub fn style(mut self, style: &BoxStyle) -> Self {
+        self.style.overlay(style);
+        self
+    }
+
+    pub fn get_style(&self) -> &BoxStyle {
+        &self.style
+    }
+
+    /// Outline the curved edges of the area
+    pub fn edge_style(mut self, other: LineStyle) -> Self {
+        if let Some(ref mut self_style) = self.edge_style {
+            self_style.overlay(&other);
+        } else {
+            self.edge_style = Some(other);
+        }
+        self
+    }
+
+    pub fn get_edge_style(&self) -> Option<&LineStyle> {
+        self.edge_style.as_ref()
+    }
+
+    /// Set the label shown for this in the legend
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.legend = Some(legend.into());
+        self
+    }
+
+    /// The corners of the filled shape, along the upper curve and back along the lower edge
+    fn outline(&self) -> Vec<(f64, f64)> {
+        let mut corners = self.data.clone();
+        match self.base {
+            AreaBase::Constant(y) => {
+                if let (Some(&(first, _)), Some(&(last, _))) = (self.data.first(), self.data.last())
+                {
+                    corners.push((last, y));
+                    corners.push((first, y));
+                }
+            }
+            AreaBase::Curve(ref lower) => corners.extend(lower.iter().rev()),
+        }
+        corners
+    }
+}
+
+impl ContinuousRepresentation for Area {
+    fn range(&self, dim: u32) -> (f64, f64) {
+        let value = match dim {
+            0 => |&(x, _): &(f64, f64)| x,
+            1 => |&(_, y): &(f64, f64)| y,
+            _ => panic!("Axis out of range"),
+        };
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for v in self.outline().iter().map(value) {
+            min = min.min(v);
+            max = max.max(v);
+        }
+        (min, max)
+    }
+
+    fn draw(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    ) {
+        // The edges are drawn as lines, so the fill itself is not outlined
+        let fill_style = self.style.clone().outline("none");
+        render::draw_face_polygon(&self.outline(), x_axis, y_axis, surface, &fill_style);
+
+        if let Some(ref edge_style) = self.edge_style {
+            render::draw_face_line(&self.data, x_axis, y_axis, surface, edge_style);
+            if let AreaBase::Curve(ref lower) = self.base {
+                render::draw_face_line(lower, x_axis, y_axis, surface, edge_style);
+            }
+        }
+    }
+
+    fn legend(&self) -> Option<LegendEntry> {
+        let outline = self
+            .edge_style
+            .as_ref()
+            .map_or_else(|| "none".into(), |style| style.get_colour());
+        self.legend.as_ref().map(|legend| {
+            LegendEntry::swatch(legend.as_str(), &self.style.clone().outline(outline))
+        })
+    }
+}
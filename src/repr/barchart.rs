@@ -15,17 +15,16 @@ let v = CategoricalView::new().add(b1).add(b2);
 
 use std::f64;
 
-use svg;
-
 use crate::axis;
+use crate::render::{self, LegendEntry, Surface};
 use crate::repr::CategoricalRepresentation;
 use crate::style::BoxStyle;
-use crate::svg_render;
 
 pub struct BarChart {
     value: f64,
     label: String,
     style: BoxStyle,
+    legend: Option<String>,
 }
 
 impl BarChart {
@@ -34,6 +33,7 @@ impl BarChart {
             value: v,
             style: BoxStyle::new(),
             label: String::new(),
+            legend: None,
         }
     }
 
@@ -58,6 +58,15 @@ impl BarChart {
         &self.label
     }
 
+    /// Set the label shown for this in the legend
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.legend = Some(legend.into());
+        self
+    }
+
     fn get_value(&self) -> f64 {
         self.value
     }
@@ -74,31 +83,25 @@ impl CategoricalRepresentation for BarChart {
         vec![self.label.clone()]
     }
 
-    fn to_svg(
+    fn draw(
         &self,
         x_axis: &axis::CategoricalAxis,
         y_axis: &axis::ContinuousAxis,
-        face_width: f64,
-        face_height: f64,
-    ) -> svg::node::element::Group {
-        svg_render::draw_face_barchart(
+        surface: &mut dyn Surface,
+    ) {
+        render::draw_face_barchart(
             self.get_value(),
             &self.label,
             x_axis,
             y_axis,
-            face_width,
-            face_height,
+            surface,
             &self.style,
         )
     }
 
-    fn to_text(
-        &self,
-        _x_axis: &axis::CategoricalAxis,
-        _y_axis: &axis::ContinuousAxis,
-        _face_width: u32,
-        _face_height: u32,
-    ) -> String {
-        "".into()
+    fn legend(&self) -> Option<LegendEntry> {
+        self.legend
+            .as_ref()
+            .map(|legend| LegendEntry::swatch(legend.as_str(), &self.style))
     }
 }
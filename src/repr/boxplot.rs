@@ -11,18 +11,44 @@ let b1 = BoxPlot::from_slice(&[0., 2., 3., 4.]);
 let b2 = BoxPlot::from_vec(vec![0., 2., 3., 4.]);
 let v = CategoricalView::new().add(b1);
 ```
+
+By default the whiskers reach the smallest and largest values.
+They can instead follow the Tukey rule, with anything beyond them drawn as an outlier,
+and the mean and a notch around the median can be shown too:
+
+```
+# use plotlib::repr::{BoxPlot, WhiskerRule};
+# use plotlib::style::{PointMarker, PointStyle};
+let latencies = vec![12., 14., 15., 15., 16., 18., 19., 22., 48., 95.];
+let b = BoxPlot::from_vec(latencies)
+    .whiskers(WhiskerRule::Tukey)
+    .outlier_style(PointStyle::new().marker(PointMarker::Cross))
+    .mean_style(PointStyle::new().marker(PointMarker::Diamond).colour("red"))
+    .notched(true);
+```
 */
 
 use std::f64;
 
-use svg;
-
 use crate::axis;
+use crate::errors::{Error, Result};
+use crate::render::{self, LegendEntry, Surface};
 use crate::repr::CategoricalRepresentation;
-use crate::style::BoxStyle;
-use crate::svg_render;
+use crate::style::{BoxStyle, PointStyle};
 use crate::utils;
 
+/// Where the whiskers of a `BoxPlot` end
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WhiskerRule {
+    /// At the smallest and largest values
+    MinMax,
+    /// At the furthest values within 1.5 times the interquartile range of the box
+    Tukey,
+    /// At the given lower and upper percentiles, from 0 to 100.
+    /// These must be outside of the box, so the lower is from 0 to 25 and the upper from 75 to 100.
+    Percentiles(f64, f64),
+}
+
 enum BoxData<'a> {
     Owned(Vec<f64>),
     Ref(&'a [f64]),
@@ -32,6 +58,11 @@ pub struct BoxPlot<'a> {
     data: BoxData<'a>,
     label: String,
     style: BoxStyle,
+    legend: Option<String>,
+    whiskers: WhiskerRule,
+    outlier_style: PointStyle,
+    mean_style: Option<PointStyle>,
+    notched: bool,
 }
 
 impl<'a> BoxPlot<'a> {
@@ -40,6 +71,11 @@ impl<'a> BoxPlot<'a> {
             data: BoxData::Ref(v),
             style: BoxStyle::new(),
             label: String::new(),
+            legend: None,
+            whiskers: WhiskerRule::MinMax,
+            outlier_style: PointStyle::new(),
+            mean_style: None,
+            notched: false,
         }
     }
 
@@ -48,6 +84,11 @@ impl<'a> BoxPlot<'a> {
             data: BoxData::Owned(v),
             style: BoxStyle::new(),
             label: String::new(),
+            legend: None,
+            whiskers: WhiskerRule::MinMax,
+            outlier_style: PointStyle::new(),
+            mean_style: None,
+            notched: false,
         }
     }
 
@@ -72,6 +113,100 @@ impl<'a> BoxPlot<'a> {
         &self.label
     }
 
+    /// Set the label shown for this in the legend
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.legend = Some(legend.into());
+        self
+    }
+
+    /// Set where the whiskers end. Any values beyond them are drawn as outliers.
+    pub fn whiskers(mut self, rule: WhiskerRule) -> Self {
+        self.whiskers = rule;
+        self
+    }
+
+    pub fn get_whiskers(&self) -> WhiskerRule {
+        self.whiskers
+    }
+
+    /// Set the style of the markers for values beyond the whiskers
+    pub fn outlier_style(mut self, style: PointStyle) -> Self {
+        self.outlier_style.overlay(&style);
+        self
+    }
+
+    pub fn get_outlier_style(&self) -> &PointStyle {
+        &self.outlier_style
+    }
+
+    /// Mark the mean of the values with a marker in the given style
+    pub fn mean_style(mut self, style: PointStyle) -> Self {
+        if let Some(ref mut self_style) = self.mean_style {
+            self_style.overlay(&style);
+        } else {
+            self.mean_style = Some(style);
+        }
+        self
+    }
+
+    pub fn get_mean_style(&self) -> Option<&PointStyle> {
+        self.mean_style.as_ref()
+    }
+
+    /// Notch the box around the median, showing its 95% confidence interval
+    pub fn notched(mut self, notched: bool) -> Self {
+        self.notched = notched;
+        self
+    }
+
+    pub fn is_notched(&self) -> bool {
+        self.notched
+    }
+
+    /// The box and whiskers for the data, along with the values beyond the whiskers
+    fn shape(&self) -> (render::BoxShape, Vec<f64>) {
+        let data = self.get_data();
+        let (q1, median, q3) = utils::quartiles(data);
+        let iqr = q3 - q1;
+        let whiskers = match self.whiskers {
+            WhiskerRule::MinMax => utils::range(data),
+            WhiskerRule::Tukey => {
+                let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+                let inside: Vec<f64> = data
+                    .iter()
+                    .cloned()
+                    .filter(|&v| v >= lower && v <= upper)
+                    .collect();
+                utils::range(&inside)
+            }
+            WhiskerRule::Percentiles(lower, upper) => (
+                utils::percentile(data, lower),
+                utils::percentile(data, upper),
+            ),
+        };
+        let outliers = data
+            .iter()
+            .cloned()
+            .filter(|&v| v < whiskers.0 || v > whiskers.1)
+            .collect();
+        // The usual approximation to the 95% confidence interval of the median
+        let notch = if self.notched {
+            let half_width = 1.57 * iqr / (data.len() as f64).sqrt();
+            Some((median - half_width, median + half_width))
+        } else {
+            None
+        };
+        let shape = render::BoxShape {
+            quartiles: (q1, median, q3),
+            whiskers,
+            notch,
+        };
+        (shape, outliers)
+    }
+
     fn get_data(&'a self) -> &'a [f64] {
         match self.data {
             BoxData::Owned(ref v) => v,
@@ -98,31 +233,49 @@ impl<'a> CategoricalRepresentation for BoxPlot<'a> {
         vec![self.label.clone()]
     }
 
-    fn to_svg(
+    /// Whiskers at percentiles must reach at least as far as the box
+    fn check(&self) -> Result<()> {
+        match self.whiskers {
+            WhiskerRule::Percentiles(lower, upper)
+                if !((0. ..=25.).contains(&lower) && (75. ..=100.).contains(&upper)) =>
+            {
+                Err(Error::InvalidWhiskers { lower, upper })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn draw(
         &self,
         x_axis: &axis::CategoricalAxis,
         y_axis: &axis::ContinuousAxis,
-        face_width: f64,
-        face_height: f64,
-    ) -> svg::node::element::Group {
-        svg_render::draw_face_boxplot(
-            self.get_data(),
+        surface: &mut dyn Surface,
+    ) {
+        let (shape, outliers) = self.shape();
+        render::draw_face_boxplot(&shape, &self.label, x_axis, y_axis, surface, &self.style);
+        render::draw_face_category_points(
+            &outliers,
             &self.label,
             x_axis,
             y_axis,
-            face_width,
-            face_height,
-            &self.style,
-        )
+            surface,
+            &self.outlier_style,
+        );
+        if let Some(ref mean_style) = self.mean_style {
+            render::draw_face_category_points(
+                &[utils::mean(self.get_data())],
+                &self.label,
+                x_axis,
+                y_axis,
+                surface,
+                mean_style,
+            );
+        }
     }
 
-    fn to_text(
-        &self,
-        _x_axis: &axis::CategoricalAxis,
-        _y_axis: &axis::ContinuousAxis,
-        _face_width: u32,
-        _face_height: u32,
-    ) -> String {
-        "".into()
+    fn legend(&self) -> Option<LegendEntry> {
+        self.legend
+            .as_ref()
+            .map(|legend| LegendEntry::swatch(legend.as_str(), &self.style))
     }
 }
@@ -22,12 +22,10 @@ TODO:
 
 use std;
 
-use svg;
-
 use crate::axis;
+use crate::render::{self, LegendEntry, Surface};
 use crate::repr::ContinuousRepresentation;
 use crate::style::BoxStyle;
-use crate::svg_render;
 use crate::text_render;
 use crate::utils::PairWise;
 
@@ -53,6 +51,7 @@ pub struct Histogram {
     pub bin_densities: Vec<f64>, // will have N_bins entries
     style: BoxStyle,
     h_type: HistogramType,
+    legend: Option<String>,
 }
 
 impl Histogram {
@@ -99,6 +98,7 @@ impl Histogram {
             bin_densities: density_per_bin,
             style: BoxStyle::new(),
             h_type: HistogramType::Count,
+            legend: None,
         }
     }
 
@@ -138,6 +138,15 @@ impl Histogram {
         &self.style
     }
 
+    /// Set the label shown for this in the legend
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.legend = Some(legend.into());
+        self
+    }
+
     pub fn get_values(&self) -> &[f64] {
         match self.h_type {
             HistogramType::Count => &self.bin_counts,
@@ -155,28 +164,46 @@ impl ContinuousRepresentation for Histogram {
         }
     }
 
-    fn to_svg(
-        &self,
-        x_axis: &axis::ContinuousAxis,
-        y_axis: &axis::ContinuousAxis,
-        face_width: f64,
-        face_height: f64,
-    ) -> svg::node::element::Group {
-        svg_render::draw_face_bars(self, x_axis, y_axis, face_width, face_height, &self.style)
-    }
-    fn legend_svg(&self) -> Option<svg::node::element::Group> {
-        // TODO implement
-        None
+    /// Empty bins have no bar so only the non-zero counts need to be shown
+    fn data_range(&self, dim: u32) -> (f64, f64) {
+        match dim {
+            1 => self
+                .get_values()
+                .iter()
+                .filter(|&&v| v != 0.)
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                    (min.min(v), max.max(v))
+                }),
+            _ => self.range(dim),
+        }
     }
 
-    fn to_text(
+    fn draw(
         &self,
         x_axis: &axis::ContinuousAxis,
         y_axis: &axis::ContinuousAxis,
-        face_width: u32,
-        face_height: u32,
-    ) -> String {
-        text_render::render_face_bars(self, x_axis, y_axis, face_width, face_height)
+        surface: &mut dyn Surface,
+    ) {
+        if surface.is_text() {
+            // Text bars are drawn as joined-up steps rather than as separate boxes
+            let (face_width, face_height) = surface.size();
+            let rows = text_render::render_face_bars(
+                self,
+                x_axis,
+                y_axis,
+                face_width as u32,
+                face_height as u32,
+            );
+            render::draw_face_characters(&rows, surface);
+        } else {
+            render::draw_face_bars(self, x_axis, y_axis, surface, &self.style)
+        }
+    }
+
+    fn legend(&self) -> Option<LegendEntry> {
+        self.legend
+            .as_ref()
+            .map(|legend| LegendEntry::swatch(legend.as_str(), &self.style))
     }
 }
 
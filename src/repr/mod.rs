@@ -4,24 +4,38 @@
 Each type that implements `Representation` or `CategoricalRepresentation` knows how to read in data
 and convert that into a concrete element to be incorporated into a larger plot.
 
-For example the `scatter::Scatter` representation can be created from a list of coordinates.
-When `to_svg()` is called on it, it will create the SVG elements showing the points from within
-the range that was requested by the caller.
+For example the `Plot` representation can be created from a list of coordinates.
+When `draw()` is called on it, it will draw the points from within the range that was requested
+by the caller onto a [`Surface`](../render/trait.Surface.html).
+The provided `to_svg()` and `to_text()` methods use this to render the representation
+as SVG elements or as text.
 
-These points may then be layered with other SVG elements from other representations into a
+These points may then be layered with the output of other representations into a
 `view::View`.
 */
 
 use crate::axis;
+use crate::errors::Result;
+use crate::render::{LegendEntry, Surface};
+use crate::svg_render::SvgSurface;
+use crate::text_render::TextSurface;
 
+mod area;
 mod barchart;
 mod boxplot;
 mod histogram;
+mod multibarchart;
 mod plot;
+mod stripplot;
+mod violin;
+pub use area::*;
 pub use barchart::*;
 pub use boxplot::*;
 pub use histogram::*;
+pub use multibarchart::*;
 pub use plot::*;
+pub use stripplot::*;
+pub use violin::*;
 
 /**
 A representation of data that is continuous in two dimensions.
@@ -30,16 +44,44 @@ pub trait ContinuousRepresentation {
     /// The maximum range in each dimension. Used for auto-scaling axes.
     fn range(&self, dim: u32) -> (f64, f64);
 
+    /// The range of the data values themselves in each dimension.
+    ///
+    /// This differs from `range` for representations which also need to show a baseline,
+    /// such as the zero that histogram bars rise from.
+    /// It is used to check that the data can be shown on an axis, e.g. one with a logarithmic scale.
+    fn data_range(&self, dim: u32) -> (f64, f64) {
+        self.range(dim)
+    }
+
+    /// Check that the representation is consistent enough to be drawn
+    fn check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Draw the representation onto the face of a view
+    fn draw(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    );
+
+    /// Returns None if no legend has been specified for this representation
+    fn legend(&self) -> Option<LegendEntry> {
+        None
+    }
+
     fn to_svg(
         &self,
         x_axis: &axis::ContinuousAxis,
         y_axis: &axis::ContinuousAxis,
         face_width: f64,
         face_height: f64,
-    ) -> svg::node::element::Group;
-
-    /// Returns None if no legend has been specified for this representation
-    fn legend_svg(&self) -> Option<svg::node::element::Group>;
+    ) -> svg::node::element::Group {
+        let mut surface = SvgSurface::new(face_width, face_height);
+        self.draw(x_axis, y_axis, &mut surface);
+        surface.into_group()
+    }
 
     fn to_text(
         &self,
@@ -47,7 +89,11 @@ pub trait ContinuousRepresentation {
         y_axis: &axis::ContinuousAxis,
         face_width: u32,
         face_height: u32,
-    ) -> String;
+    ) -> String {
+        let mut surface = TextSurface::new(face_width, face_height);
+        self.draw(x_axis, y_axis, &mut surface);
+        surface.to_string()
+    }
 }
 
 /**
@@ -60,13 +106,40 @@ pub trait CategoricalRepresentation {
     /// The ticks that this representation covers. Used to collect all ticks for display.
     fn ticks(&self) -> Vec<String>;
 
+    /// Check that the representation is consistent enough to be drawn
+    fn check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Draw the representation onto the face of a view
+    fn draw(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    );
+
+    fn legend(&self) -> Option<LegendEntry> {
+        None
+    }
+
+    /// All of the entries this representation adds to the legend.
+    /// Representations with several series return an entry for each.
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.legend().into_iter().collect()
+    }
+
     fn to_svg(
         &self,
         x_axis: &axis::CategoricalAxis,
         y_axis: &axis::ContinuousAxis,
         face_width: f64,
         face_height: f64,
-    ) -> svg::node::element::Group;
+    ) -> svg::node::element::Group {
+        let mut surface = SvgSurface::new(face_width, face_height);
+        self.draw(x_axis, y_axis, &mut surface);
+        surface.into_group()
+    }
 
     fn to_text(
         &self,
@@ -74,5 +147,9 @@ pub trait CategoricalRepresentation {
         y_axis: &axis::ContinuousAxis,
         face_width: u32,
         face_height: u32,
-    ) -> String;
+    ) -> String {
+        let mut surface = TextSurface::new(face_width, face_height);
+        self.draw(x_axis, y_axis, &mut surface);
+        surface.to_string()
+    }
 }
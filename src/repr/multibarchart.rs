@@ -0,0 +1,238 @@
+/*!
+
+Bar charts with several series of values per category
+
+# Examples
+
+```
+# use plotlib::repr::{BarMode, BarSeries, MultiBarChart};
+# use plotlib::style::BoxStyle;
+# use plotlib::view::CategoricalView;
+let b = MultiBarChart::new(vec!["Team A", "Team B", "Team C"])
+    .series(
+        BarSeries::new(vec![4., 2.5, 3.])
+            .style(&BoxStyle::new().fill("steelblue"))
+            .legend("CPU"),
+    )
+    .series(
+        BarSeries::new(vec![1., 3., 2.])
+            .style(&BoxStyle::new().fill("darkorange"))
+            .legend("Memory"),
+    )
+    .mode(BarMode::Stacked);
+let v = CategoricalView::new().add(b);
+```
+*/
+
+use std::f64;
+
+use crate::axis;
+use crate::render::{self, LegendEntry, Surface};
+use crate::repr::CategoricalRepresentation;
+use crate::style::BoxStyle;
+
+/// The fraction of the space given to each category which a group of bars covers
+const GROUP_WIDTH: f64 = 0.8;
+
+/// How the series of a `MultiBarChart` are laid out within each category
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarMode {
+    /// The bars of each series side by side
+    Grouped,
+    /// The bars of each series stacked on top of each other, building up to their total
+    Stacked,
+}
+
+/// One series of values in a `MultiBarChart`, with a value for each category
+#[derive(Debug, Clone)]
+pub struct BarSeries {
+    values: Vec<f64>,
+    style: BoxStyle,
+    legend: Option<String>,
+}
+
+impl BarSeries {
+    pub fn new(values: Vec<f64>) -> Self {
+        BarSeries {
+            values,
+            style: BoxStyle::new(),
+            legend: None,
+        }
+    }
+
+    pub fn style(mut self, style: &BoxStyle) -> Self {
+        self.style.overlay(style);
+        self
+    }
+
+    pub fn get_style(&self) -> &BoxStyle {
+        &self.style
+    }
+
+    /// Set the label shown for this series in the legend
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.legend = Some(legend.into());
+        self
+    }
+
+    pub fn get_values(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+/// A bar chart with a bar for each series in each category
+///
+/// Series with fewer values than there are categories have no bar in the remaining categories.
+#[derive(Debug, Clone)]
+pub struct MultiBarChart {
+    labels: Vec<String>,
+    series: Vec<BarSeries>,
+    mode: BarMode,
+}
+
+impl MultiBarChart {
+    /// Create a bar chart over the given categories, grouped by default
+    pub fn new<T>(labels: Vec<T>) -> Self
+    where
+        T: Into<String>,
+    {
+        MultiBarChart {
+            labels: labels.into_iter().map(Into::into).collect(),
+            series: vec![],
+            mode: BarMode::Grouped,
+        }
+    }
+
+    /// Add a series of values, in the order of the categories
+    pub fn series(mut self, series: BarSeries) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    pub fn mode(mut self, mode: BarMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn get_mode(&self) -> BarMode {
+        self.mode
+    }
+
+    pub fn get_series(&self) -> &[BarSeries] {
+        &self.series
+    }
+
+    /// The values of each series in a category, paired with the series
+    fn category_values(&self, category: usize) -> impl Iterator<Item = (&BarSeries, f64)> {
+        self.series
+            .iter()
+            .filter_map(move |series| series.values.get(category).map(|&v| (series, v)))
+    }
+
+    /// The segments of each series in a category, stacking positive values upwards
+    /// and negative values downwards from zero
+    fn stacked_segments(&self, category: usize) -> Vec<(&BarSeries, (f64, f64))> {
+        let (mut above, mut below) = (0., 0.);
+        self.category_values(category)
+            .map(|(series, v)| {
+                let total = if v >= 0. { &mut above } else { &mut below };
+                let start = *total;
+                *total += v;
+                (series, (start, *total))
+            })
+            .collect()
+    }
+}
+
+impl CategoricalRepresentation for MultiBarChart {
+    /// The maximum range. Used for auto-scaling axis
+    fn range(&self) -> (f64, f64) {
+        let mut min = 0.0f64;
+        let mut max = 0.0f64;
+        for category in 0..self.labels.len() {
+            match self.mode {
+                BarMode::Grouped => {
+                    for (_, v) in self.category_values(category) {
+                        min = min.min(v);
+                        max = max.max(v);
+                    }
+                }
+                BarMode::Stacked => {
+                    for (_, (start, end)) in self.stacked_segments(category) {
+                        min = min.min(start.min(end));
+                        max = max.max(start.max(end));
+                    }
+                }
+            }
+        }
+        (min, max)
+    }
+
+    /// The ticks that this representation covers. Used to collect all ticks for display
+    fn ticks(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+
+    fn draw(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    ) {
+        let bar_width = GROUP_WIDTH / self.series.len().max(1) as f64;
+        for (category, label) in self.labels.iter().enumerate() {
+            match self.mode {
+                BarMode::Grouped => {
+                    for (i, series) in self.series.iter().enumerate() {
+                        if let Some(&v) = series.values.get(category) {
+                            let left = i as f64 * bar_width - GROUP_WIDTH / 2.;
+                            render::draw_face_bar(
+                                label,
+                                (y_axis.baseline(), v),
+                                (left, bar_width),
+                                x_axis,
+                                y_axis,
+                                surface,
+                                &series.style,
+                            );
+                        }
+                    }
+                }
+                BarMode::Stacked => {
+                    for (series, (start, end)) in self.stacked_segments(category) {
+                        // The bottom of each stack sits on the axis, as a single bar would
+                        let start = if start == 0. {
+                            y_axis.baseline()
+                        } else {
+                            start
+                        };
+                        render::draw_face_bar(
+                            label,
+                            (start, end),
+                            (-0.25, 0.5),
+                            x_axis,
+                            y_axis,
+                            surface,
+                            &series.style,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.series
+            .iter()
+            .filter_map(|series| {
+                series
+                    .legend
+                    .as_ref()
+                    .map(|legend| LegendEntry::swatch(legend.as_str(), &series.style))
+            })
+            .collect()
+    }
+}
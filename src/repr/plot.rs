@@ -9,19 +9,28 @@
 //! let l = Plot::new(vec![(0., 1.), (2., 1.5), (3., 1.2), (4., 1.1)]);
 //! let v = ContinuousView::new().add(l);
 //! ```
+//!
+//! Error bars can be added to the points, either the same distance either side of each point
+//! or with separate distances below and above it:
+//!
+//! ```
+//! # use plotlib::repr::Plot;
+//! # use plotlib::style::{LineStyle, PointStyle};
+//! let p = Plot::new(vec![(1., 2.), (2., 3.5), (3., 3.)])
+//!     .point_style(PointStyle::new())
+//!     .y_errors(vec![0.2, 0.4, 0.3])
+//!     .x_errors_asymmetric(vec![(0.1, 0.2), (0.1, 0.1), (0.2, 0.1)])
+//!     .error_style(LineStyle::new().colour("grey"))
+//!     .error_cap_width(4.);
+//! ```
 
 use std::f64;
 
-use svg;
-use svg::node;
-use svg::Node;
-
 use crate::axis;
+use crate::errors::{Error, Result};
+use crate::render::{self, LegendEntry, Surface};
 use crate::repr::ContinuousRepresentation;
 use crate::style::*;
-use crate::svg_render;
-use crate::text_render;
-use crate::svg_render::draw_marker;
 
 /// Representation of any plot with points in the XY plane, visualized as points and/or with lines
 /// in-between.
@@ -33,6 +42,14 @@ pub struct Plot {
     /// None if no points should be displayed
     pub point_style: Option<PointStyle>,
     pub legend: Option<String>,
+    /// The distances below and above each x value, if there are horizontal error bars
+    pub x_errors: Option<Vec<(f64, f64)>>,
+    /// The distances below and above each y value, if there are vertical error bars
+    pub y_errors: Option<Vec<(f64, f64)>>,
+    /// The style of the lines of the error bars
+    pub error_style: LineStyle,
+    /// The length of the caps across the ends of the error bars
+    pub error_cap_width: f64,
 }
 
 impl Plot {
@@ -42,6 +59,10 @@ impl Plot {
             line_style: None,
             point_style: None,
             legend: None,
+            x_errors: None,
+            y_errors: None,
+            error_style: LineStyle::new(),
+            error_cap_width: 6.,
         }
     }
 
@@ -51,12 +72,7 @@ impl Plot {
             .map(|x| lower + (f64::from(x) * sampling))
             .take_while(|&x| x <= upper);
         let values = samples.map(|s| (s, f(s))).collect();
-        Plot {
-            data: values,
-            line_style: None,
-            point_style: None,
-            legend: None,
-        }
+        Plot::new(values)
     }
 
     pub fn line_style(mut self, other: LineStyle) -> Self {
@@ -80,24 +96,75 @@ impl Plot {
         self
     }
 
-    fn x_range(&self) -> (f64, f64) {
+    /// Add horizontal error bars reaching the same distance either side of each point
+    ///
+    /// There must be one for each point, or the view cannot be rendered.
+    pub fn x_errors(self, errors: Vec<f64>) -> Self {
+        self.x_errors_asymmetric(errors.into_iter().map(|e| (e, e)).collect())
+    }
+
+    /// Add horizontal error bars reaching separate distances below and above the x value of each point
+    ///
+    /// There must be one for each point, or the view cannot be rendered.
+    pub fn x_errors_asymmetric(mut self, errors: Vec<(f64, f64)>) -> Self {
+        self.x_errors = Some(errors);
+        self
+    }
+
+    /// Add vertical error bars reaching the same distance either side of each point
+    ///
+    /// There must be one for each point, or the view cannot be rendered.
+    pub fn y_errors(self, errors: Vec<f64>) -> Self {
+        self.y_errors_asymmetric(errors.into_iter().map(|e| (e, e)).collect())
+    }
+
+    /// Add vertical error bars reaching separate distances below and above the y value of each point
+    ///
+    /// There must be one for each point, or the view cannot be rendered.
+    pub fn y_errors_asymmetric(mut self, errors: Vec<(f64, f64)>) -> Self {
+        self.y_errors = Some(errors);
+        self
+    }
+
+    /// Set the style of the lines of the error bars
+    pub fn error_style(mut self, other: LineStyle) -> Self {
+        self.error_style.overlay(&other);
+        self
+    }
+
+    /// Set the length of the caps across the ends of the error bars, which may be zero for no caps
+    pub fn error_cap_width(mut self, width: f64) -> Self {
+        self.error_cap_width = width;
+        self
+    }
+
+    /// The extent of the points along one dimension, including any error bars
+    fn dim_range(
+        &self,
+        errors: &Option<Vec<(f64, f64)>>,
+        value: fn(&(f64, f64)) -> f64,
+    ) -> (f64, f64) {
         let mut min = f64::INFINITY;
         let mut max = f64::NEG_INFINITY;
-        for &(x, _) in &self.data {
-            min = min.min(x);
-            max = max.max(x);
+        for (i, point) in self.data.iter().enumerate() {
+            let v = value(point);
+            let (below, above) = errors
+                .as_ref()
+                .and_then(|e| e.get(i))
+                .cloned()
+                .unwrap_or((0., 0.));
+            min = min.min(v - below);
+            max = max.max(v + above);
         }
         (min, max)
     }
 
+    fn x_range(&self) -> (f64, f64) {
+        self.dim_range(&self.x_errors, |&(x, _)| x)
+    }
+
     fn y_range(&self) -> (f64, f64) {
-        let mut min = f64::INFINITY;
-        let mut max = f64::NEG_INFINITY;
-        for &(_, y) in &self.data {
-            min = min.min(y);
-            max = max.max(y);
-        }
-        (min, max)
+        self.dim_range(&self.y_errors, |&(_, y)| y)
     }
 }
 
@@ -110,98 +177,53 @@ impl ContinuousRepresentation for Plot {
         }
     }
 
-    fn to_svg(
+    /// There must be an error for each point in each dimension which has error bars
+    fn check(&self) -> Result<()> {
+        for (name, errors) in [("x", &self.x_errors), ("y", &self.y_errors)] {
+            if let Some(errors) = errors {
+                if errors.len() != self.data.len() {
+                    return Err(Error::InvalidErrorBars {
+                        name: String::from(name),
+                        errors: errors.len(),
+                        points: self.data.len(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(
         &self,
         x_axis: &axis::ContinuousAxis,
         y_axis: &axis::ContinuousAxis,
-        face_width: f64,
-        face_height: f64,
-    ) -> svg::node::element::Group {
-        let mut group = node::element::Group::new();
+        surface: &mut dyn Surface,
+    ) {
         if let Some(ref line_style) = self.line_style {
-            group.append(svg_render::draw_face_line(
-                &self.data,
-                x_axis,
-                y_axis,
-                face_width,
-                face_height,
-                line_style,
-            ))
+            render::draw_face_line(&self.data, x_axis, y_axis, surface, line_style);
         }
+        let cap_width = if surface.is_text() {
+            // In text the caps on error bars are always a few characters across
+            if self.error_cap_width > 0. {
+                2.
+            } else {
+                0.
+            }
+        } else {
+            self.error_cap_width
+        };
+        render::draw_face_error_bars(self, x_axis, y_axis, surface, cap_width);
         if let Some(ref point_style) = self.point_style {
-            group.append(svg_render::draw_face_points(
-                &self.data,
-                x_axis,
-                y_axis,
-                face_width,
-                face_height,
-                point_style,
-            ))
+            render::draw_face_points(&self.data, x_axis, y_axis, surface, point_style);
         }
-        group
-    }
-    fn legend_svg(&self) -> Option<svg::node::element::Group> {
-        // TODO: add points
-        // TODO: can we use common functionality with svg_render?
-        self.legend.as_ref().map(|legend| {
-            let legend = legend.clone();
-
-            let mut group = node::element::Group::new();
-            const FONT_SIZE: f32 = 9.0;
-
-            // Draw legend text
-            let legend_text = node::element::Text::new()
-                .set("x", 0)
-                .set("y", 0)
-                .set("text-anchor", "start")
-                .set("font-size", FONT_SIZE)
-                .add(node::Text::new(legend));
-            group.append(legend_text);
-
-            if let Some(ref style) = self.line_style {
-                let line = node::element::Line::new()
-                    .set("x1", -23)
-                    .set("y1", -FONT_SIZE / 2. + 2.)
-                    .set("x2", -3)
-                    .set("y2", -FONT_SIZE / 2. + 2.)
-                    .set("stroke-width", style.get_width())
-                    .set("stroke", style.get_colour());
-                group.append(line);
-            }
-
-            if let Some(ref style) = self.point_style {
-                let mark = draw_marker(-13., (-FONT_SIZE / 2. + 2.) as f64, style);
-                group.append(mark);
-            }
-
-            group
-        })
     }
 
-    fn to_text(
-        &self,
-        x_axis: &axis::ContinuousAxis,
-        y_axis: &axis::ContinuousAxis,
-        face_width: u32,
-        face_height: u32,
-    ) -> String {
-        let face_lines = if let Some(line_style) = &self.line_style {
-            unimplemented!("Text rendering does not yet support line plots")
-        } else {
-            text_render::empty_face(face_width, face_height)
-        };
-        let face_points = if let Some(point_style) = &self.point_style {
-            text_render::render_face_points(
-                &self.data,
-                x_axis,
-                y_axis,
-                face_width,
-                face_height,
-                point_style,
-            )
-        } else {
-            text_render::empty_face(face_width, face_height)
-        };
-        text_render::overlay(&face_lines, &face_points, 0, 0)
+    fn legend(&self) -> Option<LegendEntry> {
+        self.legend.as_ref().map(|legend| LegendEntry {
+            label: legend.clone(),
+            line_style: self.line_style.clone(),
+            point_style: self.point_style.clone(),
+            box_style: None,
+        })
     }
 }
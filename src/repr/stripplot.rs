@@ -0,0 +1,165 @@
+/*!
+
+Strip plot, showing every value as a marker at its category
+
+# Examples
+
+```
+# use plotlib::repr::{BoxPlot, StripLayout, StripPlot};
+# use plotlib::style::PointStyle;
+# use plotlib::view::CategoricalView;
+let runs = vec![10.2, 11.5, 9.8, 10.9, 14.1, 10.4];
+let b = BoxPlot::from_vec(runs.clone()).label("baseline");
+let s = StripPlot::from_vec(runs)
+    .label("baseline")
+    .layout(StripLayout::Swarm)
+    .style(PointStyle::new().colour("darkred"));
+// The points are drawn over the box, as they share a category
+let v = CategoricalView::new().add(b).add(s);
+```
+*/
+
+use std::f64;
+
+use crate::axis;
+use crate::render::{self, LegendEntry, Surface};
+use crate::repr::CategoricalRepresentation;
+use crate::style::PointStyle;
+use crate::utils;
+
+/// How the markers of a `StripPlot` are spread across their category
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StripLayout {
+    /// Shifted randomly up to this far either side of the centre,
+    /// as a fraction of the space given to each category
+    Jitter(f64),
+    /// Packed side by side, as close to the centre as they can be without overlapping.
+    /// A swarm too wide for its category is squeezed to fit, so that its markers overlap.
+    Swarm,
+}
+
+#[derive(Debug, Clone)]
+pub struct StripPlot {
+    data: Vec<f64>,
+    label: String,
+    style: PointStyle,
+    layout: StripLayout,
+    seed: u64,
+    legend: Option<String>,
+}
+
+impl StripPlot {
+    pub fn from_slice(v: &[f64]) -> Self {
+        StripPlot::from_vec(v.to_vec())
+    }
+
+    pub fn from_vec(v: Vec<f64>) -> Self {
+        StripPlot {
+            data: v,
+            label: String::new(),
+            style: PointStyle::new(),
+            layout: StripLayout::Jitter(0.1),
+            seed: 0,
+            legend: None,
+        }
+    }
+
+    pub fn style(mut self, style: PointStyle) -> Self {
+        self.style.overlay(&style);
+        self
+    }
+
+    pub fn get_style(&self) -> &PointStyle {
+        &self.style
+    }
+
+    pub fn label<T>(mut self, label: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.label = label.into();
+        self
+    }
+
+    pub fn get_label(&self) -> &String {
+        &self.label
+    }
+
+    /// Set how the markers are spread across the category, which is a small jitter by default
+    pub fn layout(mut self, layout: StripLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn get_layout(&self) -> StripLayout {
+        self.layout
+    }
+
+    /// Set the seed of the random jitter. The same seed always gives the same output.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the label shown for this in the legend
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.legend = Some(legend.into());
+        self
+    }
+}
+
+impl CategoricalRepresentation for StripPlot {
+    /// The maximum range. Used for auto-scaling axis
+    fn range(&self) -> (f64, f64) {
+        utils::range(&self.data)
+    }
+
+    /// The ticks that this representation covers. Used to collect all ticks for display
+    fn ticks(&self) -> Vec<String> {
+        vec![self.label.clone()]
+    }
+
+    fn draw(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    ) {
+        match self.layout {
+            StripLayout::Jitter(width) => {
+                let mut rng = utils::Rng::new(self.seed);
+                let offsets: Vec<f64> = self
+                    .data
+                    .iter()
+                    .map(|_| width * (2. * rng.next_f64() - 1.))
+                    .collect();
+                render::draw_face_strip(
+                    &self.data,
+                    &offsets,
+                    &self.label,
+                    x_axis,
+                    y_axis,
+                    surface,
+                    &self.style,
+                );
+            }
+            StripLayout::Swarm => render::draw_face_swarm(
+                &self.data,
+                &self.label,
+                x_axis,
+                y_axis,
+                surface,
+                &self.style,
+            ),
+        }
+    }
+
+    fn legend(&self) -> Option<LegendEntry> {
+        self.legend
+            .as_ref()
+            .map(|legend| LegendEntry::marker(legend.as_str(), &self.style))
+    }
+}
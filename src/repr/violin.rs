@@ -0,0 +1,337 @@
+/*!
+
+Violin plot
+
+Each violin shows a kernel density estimate of the values, mirrored either side of the category.
+
+# Examples
+
+```
+# use plotlib::repr::{Violin, ViolinInner};
+# use plotlib::view::CategoricalView;
+let v1 = Violin::from_slice(&[1., 1.2, 1.4, 3.8, 4., 4.1]).label("bimodal");
+let v2 = Violin::from_vec(vec![2., 2.5, 2.6, 3., 3.1])
+    .label("unimodal")
+    .inner(ViolinInner::Quartiles)
+    .bandwidth(0.3);
+let v = CategoricalView::new().add(v1).add(v2);
+```
+
+Two distributions can be compared by splitting a violin between them:
+
+```
+# use plotlib::repr::Violin;
+# use plotlib::style::BoxStyle;
+let hits = vec![0.8, 1., 1.1, 1.3];
+let misses = vec![3.5, 4., 4.2, 5.1];
+let v = Violin::from_vec(hits)
+    .split_with(misses)
+    .label("cache")
+    .legend("Hit")
+    .style(&BoxStyle::new().fill("lightsteelblue"))
+    .split_legend("Miss")
+    .split_style(&BoxStyle::new().fill("wheat"));
+```
+*/
+
+use std::f64;
+
+use crate::axis;
+use crate::errors::{Error, Result};
+use crate::render::{self, LegendEntry, Surface};
+use crate::repr::CategoricalRepresentation;
+use crate::style::{BoxStyle, LineStyle, PointStyle};
+use crate::utils;
+
+/// How far either side of the category the widest part of a violin reaches,
+/// as a fraction of the space given to each category
+const VIOLIN_REACH: f64 = 0.4;
+
+/// How many points each density curve is estimated at
+const VIOLIN_SAMPLES: usize = 100;
+
+/// What is drawn inside a violin to summarise the values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViolinInner {
+    /// Nothing
+    None,
+    /// A narrow box plot down the middle, with a marker at the median
+    Box,
+    /// Dashed lines across the violin at the median and quartiles
+    Quartiles,
+}
+
+/// One side of a split violin, or the whole of an unsplit one
+#[derive(Debug, Clone)]
+struct ViolinHalf {
+    data: Vec<f64>,
+    style: BoxStyle,
+    legend: Option<String>,
+}
+
+impl ViolinHalf {
+    fn new(data: Vec<f64>) -> Self {
+        ViolinHalf {
+            data,
+            style: BoxStyle::new(),
+            legend: None,
+        }
+    }
+
+    fn legend_entry(&self) -> Option<LegendEntry> {
+        self.legend
+            .as_ref()
+            .map(|legend| LegendEntry::swatch(legend.as_str(), &self.style))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Violin {
+    left: ViolinHalf,
+    /// Only drawn if the violin is split, but its style and legend can be set beforehand
+    right: ViolinHalf,
+    split: bool,
+    label: String,
+    bandwidth: Option<f64>,
+    inner: ViolinInner,
+}
+
+impl Violin {
+    pub fn from_slice(v: &[f64]) -> Self {
+        Violin::from_vec(v.to_vec())
+    }
+
+    pub fn from_vec(v: Vec<f64>) -> Self {
+        Violin {
+            left: ViolinHalf::new(v),
+            right: ViolinHalf::new(vec![]),
+            split: false,
+            label: String::new(),
+            bandwidth: None,
+            inner: ViolinInner::Box,
+        }
+    }
+
+    /// Split the violin, showing these values on the right-hand side
+    /// and the original values on the left
+    pub fn split_with(mut self, v: Vec<f64>) -> Self {
+        self.right.data = v;
+        self.split = true;
+        self
+    }
+
+    /// Set the style of the violin, or of its left-hand side if it is split
+    pub fn style(mut self, style: &BoxStyle) -> Self {
+        self.left.style.overlay(style);
+        self
+    }
+
+    pub fn get_style(&self) -> &BoxStyle {
+        &self.left.style
+    }
+
+    /// Set the style of the right-hand side of a split violin
+    ///
+    /// This can be set before or after the values are given with `split_with`.
+    pub fn split_style(mut self, style: &BoxStyle) -> Self {
+        self.right.style.overlay(style);
+        self
+    }
+
+    pub fn label<T>(mut self, label: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.label = label.into();
+        self
+    }
+
+    pub fn get_label(&self) -> &String {
+        &self.label
+    }
+
+    /// Set the label shown for this in the legend, or for its left-hand side if it is split
+    pub fn legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.left.legend = Some(legend.into());
+        self
+    }
+
+    /// Set the label shown in the legend for the right-hand side of a split violin
+    ///
+    /// Like `split_style`, this can be set before or after `split_with`.
+    pub fn split_legend<T>(mut self, legend: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.right.legend = Some(legend.into());
+        self
+    }
+
+    /// Set the bandwidth of the kernel density estimate
+    ///
+    /// By default this is chosen by Silverman's rule of thumb for each side.
+    /// It must be positive, or drawing the violin fails with an error.
+    pub fn bandwidth(mut self, bandwidth: f64) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Set what is drawn inside the violin, which is a narrow box plot by default
+    pub fn inner(mut self, inner: ViolinInner) -> Self {
+        self.inner = inner;
+        self
+    }
+
+    pub fn get_inner(&self) -> ViolinInner {
+        self.inner
+    }
+
+    fn halves(&self) -> impl Iterator<Item = &ViolinHalf> {
+        std::iter::once(&self.left).chain(self.split.then_some(&self.right))
+    }
+
+    fn bandwidth_for(&self, data: &[f64]) -> f64 {
+        self.bandwidth
+            .unwrap_or_else(|| utils::silverman_bandwidth(data))
+    }
+
+    /// The density curve of some values, reaching twice the bandwidth beyond the extreme values
+    fn density_curve(&self, data: &[f64]) -> Vec<(f64, f64)> {
+        let bandwidth = self.bandwidth_for(data);
+        let (min, max) = utils::range(data);
+        let (start, end) = (min - 2. * bandwidth, max + 2. * bandwidth);
+        (0..=VIOLIN_SAMPLES)
+            .map(|i| {
+                let v = start + (end - start) * i as f64 / VIOLIN_SAMPLES as f64;
+                (v, utils::gaussian_kde(data, bandwidth, v))
+            })
+            .collect()
+    }
+}
+
+impl CategoricalRepresentation for Violin {
+    /// The maximum range. Used for auto-scaling axis
+    fn range(&self) -> (f64, f64) {
+        let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+        for half in self.halves().filter(|half| !half.data.is_empty()) {
+            let bandwidth = self.bandwidth_for(&half.data);
+            let (min, max) = utils::range(&half.data);
+            range.0 = range.0.min(min - 2. * bandwidth);
+            range.1 = range.1.max(max + 2. * bandwidth);
+        }
+        range
+    }
+
+    /// The ticks that this representation covers. Used to collect all ticks for display
+    fn ticks(&self) -> Vec<String> {
+        vec![self.label.clone()]
+    }
+
+    /// A bandwidth which has been set must be positive
+    fn check(&self) -> Result<()> {
+        match self.bandwidth {
+            Some(bandwidth) if !(bandwidth > 0. && bandwidth.is_finite()) => {
+                Err(Error::InvalidBandwidth(bandwidth))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn draw(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    ) {
+        // How far each half reaches to the left and right of the category
+        let sides = if self.split {
+            [(1., 0.), (0., 1.)]
+        } else {
+            [(1., 1.), (1., 1.)]
+        };
+        let halves: Vec<_> = self
+            .halves()
+            .zip(sides)
+            .filter(|(half, _)| !half.data.is_empty())
+            .map(|(half, sides)| (half, sides, self.density_curve(&half.data)))
+            .collect();
+        // Both sides are scaled together, so that their densities can be compared
+        let peak = halves
+            .iter()
+            .flat_map(|(_, _, curve)| curve.iter().map(|&(_, density)| density))
+            .fold(0., f64::max);
+        let scale = VIOLIN_REACH / peak;
+
+        for &(half, sides, ref curve) in &halves {
+            let curve: Vec<_> = curve.iter().map(|&(v, d)| (v, d * scale)).collect();
+            render::draw_face_violin(
+                &curve,
+                sides,
+                &self.label,
+                x_axis,
+                y_axis,
+                surface,
+                &half.style,
+            );
+
+            if self.inner == ViolinInner::Quartiles {
+                let bandwidth = self.bandwidth_for(&half.data);
+                let (q1, median, q3) = utils::quartiles(&half.data);
+                for (value, dash) in [(q1, 3.), (median, 6.), (q3, 3.)] {
+                    let reach = utils::gaussian_kde(&half.data, bandwidth, value) * scale;
+                    render::draw_face_category_line(
+                        value,
+                        (sides.0 * reach, sides.1 * reach),
+                        &self.label,
+                        x_axis,
+                        y_axis,
+                        surface,
+                        &LineStyle::new().dash(&[dash, 3.]),
+                    );
+                }
+            }
+        }
+
+        if self.inner == ViolinInner::Box {
+            let data: Vec<f64> = self.halves().flat_map(|half| half.data.clone()).collect();
+            if data.is_empty() {
+                return;
+            }
+            let (q1, median, q3) = utils::quartiles(&data);
+            let inner_style = BoxStyle::new().fill("black");
+            render::draw_face_bar(
+                &self.label,
+                utils::range(&data),
+                (-0.005, 0.01),
+                x_axis,
+                y_axis,
+                surface,
+                &inner_style,
+            );
+            render::draw_face_bar(
+                &self.label,
+                (q1, q3),
+                (-0.03, 0.06),
+                x_axis,
+                y_axis,
+                surface,
+                &inner_style,
+            );
+            render::draw_face_category_points(
+                &[median],
+                &self.label,
+                x_axis,
+                y_axis,
+                surface,
+                &PointStyle::new().colour("white").size(2.),
+            );
+        }
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.halves().filter_map(ViolinHalf::legend_entry).collect()
+    }
+}
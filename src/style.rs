@@ -18,6 +18,8 @@ pub struct LineStyle {
     pub colour: Option<String>,
     pub width: Option<f32>,
     pub linejoin: Option<LineJoin>,
+    pub dash: Option<Vec<f32>>,
+    pub opacity: Option<f32>,
 }
 impl LineStyle {
     pub fn new() -> Self {
@@ -25,6 +27,8 @@ impl LineStyle {
             colour: None,
             width: None,
             linejoin: None,
+            dash: None,
+            opacity: None,
         }
     }
 
@@ -40,6 +44,14 @@ impl LineStyle {
         if let Some(ref v) = other.linejoin {
             self.linejoin = Some(*v)
         }
+
+        if let Some(ref v) = other.dash {
+            self.dash = Some(v.clone())
+        }
+
+        if let Some(v) = other.opacity {
+            self.opacity = Some(v)
+        }
     }
     pub fn colour<T>(mut self, value: T) -> Self
     where
@@ -73,6 +85,28 @@ impl LineStyle {
     pub fn get_linejoin(&self) -> LineJoin {
         self.linejoin.unwrap_or(LineJoin::Round)
     }
+
+    /// Draw the line dashed, alternating between drawn and blank lengths given by `pattern`
+    pub fn dash(mut self, pattern: &[f32]) -> Self {
+        self.dash = Some(pattern.into());
+        self
+    }
+    /// The dash pattern of the line, which is empty for a solid line
+    pub fn get_dash(&self) -> Vec<f32> {
+        self.dash.clone().unwrap_or_default()
+    }
+
+    /// Set how opaque the line is, from 0 (invisible) to 1 (solid)
+    pub fn opacity<T>(mut self, value: T) -> Self
+    where
+        T: Into<f32>,
+    {
+        self.opacity = Some(value.into());
+        self
+    }
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity.unwrap_or(1.0)
+    }
 }
 
 /// The marker that should be used for the points of the scatter plot
@@ -150,19 +184,31 @@ impl PointStyle {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct BoxStyle {
     fill: Option<String>,
+    outline: Option<String>,
+    opacity: Option<f32>,
 }
 impl BoxStyle {
     pub fn new() -> Self {
-        BoxStyle { fill: None }
+        BoxStyle {
+            fill: None,
+            outline: None,
+            opacity: None,
+        }
     }
 
     pub fn overlay(&mut self, other: &Self) {
         if let Some(ref v) = other.fill {
             self.fill = Some(v.clone())
         }
+        if let Some(ref v) = other.outline {
+            self.outline = Some(v.clone())
+        }
+        if let Some(v) = other.opacity {
+            self.opacity = Some(v)
+        }
     }
 
     pub fn fill<T>(mut self, value: T) -> Self
@@ -175,6 +221,31 @@ impl BoxStyle {
     pub fn get_fill(&self) -> String {
         self.fill.clone().unwrap_or_else(|| "".into())
     }
+
+    /// Set the colour of the outline, which may be `"none"`
+    pub fn outline<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.outline = Some(value.into());
+        self
+    }
+    /// The colour of the outline, which is black unless set
+    pub fn get_outline(&self) -> String {
+        self.outline.clone().unwrap_or_else(|| "black".into())
+    }
+
+    /// Set how opaque the fill is, from 0 (invisible) to 1 (solid)
+    pub fn opacity<T>(mut self, value: T) -> Self
+    where
+        T: Into<f32>,
+    {
+        self.opacity = Some(value.into());
+        self
+    }
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity.unwrap_or(1.0)
+    }
 }
 
 #[cfg(test)]
@@ -1,214 +1,10 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use svg::node;
 use svg::Node;
 
-use crate::axis;
-use crate::grid::GridType;
-use crate::repr;
+use crate::render::{Surface, TextAnchor};
 use crate::style;
-use crate::utils;
-use crate::utils::PairWise;
-
-fn value_to_face_offset(value: f64, axis: &axis::ContinuousAxis, face_size: f64) -> f64 {
-    let range = axis.max() - axis.min();
-    (face_size * (value - axis.min())) / range
-}
-
-fn vertical_line<S>(xpos: f64, ymin: f64, ymax: f64, color: S) -> node::element::Line
-where
-    S: AsRef<str>,
-{
-    node::element::Line::new()
-        .set("x1", xpos)
-        .set("x2", xpos)
-        .set("y1", ymin)
-        .set("y2", ymax)
-        .set("stroke", color.as_ref())
-        .set("stroke-width", 1)
-}
-
-fn horizontal_line<S>(ypos: f64, xmin: f64, xmax: f64, color: S) -> node::element::Line
-where
-    S: AsRef<str>,
-{
-    node::element::Line::new()
-        .set("x1", xmin)
-        .set("x2", xmax)
-        .set("y1", ypos)
-        .set("y2", ypos)
-        .set("stroke", color.as_ref())
-        .set("stroke-width", 1)
-}
-
-pub fn draw_x_axis(a: &axis::ContinuousAxis, face_width: f64) -> node::element::Group {
-    let axis_line = horizontal_line(0.0, 0.0, face_width, "black");
-
-    let mut ticks = node::element::Group::new();
-    let mut labels = node::element::Group::new();
-
-    for &tick in a.ticks().iter() {
-        let tick_pos = value_to_face_offset(tick, a, face_width);
-        let tick_mark = node::element::Line::new()
-            .set("x1", tick_pos)
-            .set("y1", 0)
-            .set("x2", tick_pos)
-            .set("y2", 10)
-            .set("stroke", "black")
-            .set("stroke-width", 1);
-        ticks.append(tick_mark);
-
-        let tick_label = node::element::Text::new()
-            .set("x", tick_pos)
-            .set("y", 20)
-            .set("text-anchor", "middle")
-            .set("font-size", 12)
-            .add(node::Text::new(tick.to_string()));
-        labels.append(tick_label);
-    }
-
-    let label = node::element::Text::new()
-        .set("x", face_width / 2.)
-        .set("y", 30)
-        .set("text-anchor", "middle")
-        .set("font-size", 12)
-        .add(node::Text::new(a.get_label()));
-
-    node::element::Group::new()
-        .add(ticks)
-        .add(axis_line)
-        .add(labels)
-        .add(label)
-}
-
-pub fn draw_y_axis(a: &axis::ContinuousAxis, face_height: f64) -> node::element::Group {
-    let axis_line = vertical_line(0.0, 0.0, -face_height, "black");
-
-    let mut ticks = node::element::Group::new();
-    let mut labels = node::element::Group::new();
-
-    let y_tick_font_size = 12;
-
-    for &tick in a.ticks().iter() {
-        let tick_pos = value_to_face_offset(tick, a, face_height);
-        let tick_mark = node::element::Line::new()
-            .set("x1", 0)
-            .set("y1", -tick_pos)
-            .set("x2", -10)
-            .set("y2", -tick_pos)
-            .set("stroke", "black")
-            .set("stroke-width", 1);
-        ticks.append(tick_mark);
-
-        let tick_label = node::element::Text::new()
-            .set("x", -15)
-            .set("y", -tick_pos)
-            .set("text-anchor", "end")
-            .set("dominant-baseline", "middle")
-            .set("font-size", y_tick_font_size)
-            .add(node::Text::new(tick.to_string()));
-        labels.append(tick_label);
-    }
-
-    let max_tick_length = a
-        .ticks()
-        .iter()
-        .map(|&t| t.to_string().len())
-        .max()
-        .expect("Could not calculate max tick length");
-
-    let x_offset = -(y_tick_font_size * max_tick_length as i32);
-    let y_label_offset = -(face_height / 2.);
-    let y_label_font_size = 12;
-    let label = node::element::Text::new()
-        .set("x", x_offset)
-        .set("y", y_label_offset - f64::from(y_label_font_size))
-        .set("text-anchor", "middle")
-        .set("font-size", y_label_font_size)
-        .set(
-            "transform",
-            format!("rotate(-90 {} {})", x_offset, y_label_offset),
-        )
-        .add(node::Text::new(a.get_label()));
-
-    node::element::Group::new()
-        .add(ticks)
-        .add(axis_line)
-        .add(labels)
-        .add(label)
-}
-
-pub fn draw_categorical_x_axis(a: &axis::CategoricalAxis, face_width: f64) -> node::element::Group {
-    let axis_line = node::element::Line::new()
-        .set("x1", 0)
-        .set("y1", 0)
-        .set("x2", face_width)
-        .set("y2", 0)
-        .set("stroke", "black")
-        .set("stroke-width", 1);
-
-    let mut ticks = node::element::Group::new();
-    let mut labels = node::element::Group::new();
-
-    let space_per_tick = face_width / a.ticks().len() as f64;
-
-    for (i, tick) in a.ticks().iter().enumerate() {
-        let tick_pos = (i as f64 * space_per_tick) + (0.5 * space_per_tick);
-        let tick_mark = node::element::Line::new()
-            .set("x1", tick_pos)
-            .set("y1", 0)
-            .set("x2", tick_pos)
-            .set("y2", 10)
-            .set("stroke", "black")
-            .set("stroke-width", 1);
-        ticks.append(tick_mark);
-
-        let tick_label = node::element::Text::new()
-            .set("text-anchor", "middle")
-            .set("x", 0)
-            .set("y", 0)
-            .set("font-size", 9)
-            .set("transform", "rotate(10)")
-            .add(node::Text::new(tick.to_owned()));
-
-        let tick_g = node::element::Group::new()
-            .set("transform", format!("translate({}, {})", tick_pos, 20))
-            .add(tick_label);
-
-        labels.append(tick_g);
-    }
-
-    let label = node::element::Text::new()
-        .set("x", face_width / 2.)
-        .set("y", 30)
-        .set("text-anchor", "middle")
-        .set("font-size", 9)
-        .add(node::Text::new(a.get_label()));
-
-    node::element::Group::new()
-        .add(ticks)
-        .add(axis_line)
-        .add(labels)
-        .add(label)
-}
-
-pub fn draw_face_points(
-    s: &[(f64, f64)],
-    x_axis: &axis::ContinuousAxis,
-    y_axis: &axis::ContinuousAxis,
-    face_width: f64,
-    face_height: f64,
-    style: &style::PointStyle,
-) -> node::element::Group {
-    let mut group = node::element::Group::new();
-
-    for &(x, y) in s {
-        let x_pos = value_to_face_offset(x, x_axis, face_width);
-        let y_pos = -value_to_face_offset(y, y_axis, face_height);
-        let mark = draw_marker(x_pos, y_pos, style);
-        group.append(mark);
-    }
-
-    group
-}
 
 pub fn draw_marker(x_pos: f64, y_pos: f64, style: &style::PointStyle) -> node::element::Group  {
     let radius = f64::from(style.get_size());
@@ -365,243 +161,232 @@ pub fn draw_marker(x_pos: f64, y_pos: f64, style: &style::PointStyle) -> node::e
     group
 }
 
-pub fn draw_face_bars(
-    h: &repr::Histogram,
-    x_axis: &axis::ContinuousAxis,
-    y_axis: &axis::ContinuousAxis,
-    face_width: f64,
-    face_height: f64,
-    style: &style::BoxStyle,
-) -> node::element::Group {
-    let mut group = node::element::Group::new();
-
-    for ((&l, &u), &count) in h.bin_bounds.pairwise().zip(h.get_values()) {
-        let l_pos = value_to_face_offset(l, x_axis, face_width);
-        let u_pos = value_to_face_offset(u, x_axis, face_width);
-        let width = u_pos - l_pos;
-        let count_scaled = value_to_face_offset(count, y_axis, face_height);
-        let rect = node::element::Rectangle::new()
-            .set("x", l_pos)
-            .set("y", -count_scaled)
-            .set("width", width)
-            .set("height", count_scaled)
-            .set("fill", style.get_fill())
-            .set("stroke", "black");
-        group.append(rect);
+/// Set the dash pattern and opacity of a stroke, where they differ from a plain solid line
+fn set_stroke_style<T: Node>(mut node: T, style: &style::LineStyle) -> T {
+    let dash = style.get_dash();
+    if !dash.is_empty() {
+        let pattern: Vec<String> = dash.iter().map(|d| d.to_string()).collect();
+        node.assign("stroke-dasharray", pattern.join(" "));
     }
-
-    group
+    if style.get_opacity() < 1. {
+        node.assign("stroke-opacity", style.get_opacity());
+    }
+    node
 }
 
-pub fn draw_face_line(
-    s: &[(f64, f64)],
-    x_axis: &axis::ContinuousAxis,
-    y_axis: &axis::ContinuousAxis,
-    face_width: f64,
-    face_height: f64,
-    style: &style::LineStyle,
-) -> node::element::Group {
-    let mut group = node::element::Group::new();
-
-    let mut d: Vec<node::element::path::Command> = vec![];
-    let &(first_x, first_y) = s.first().unwrap();
-    let first_x_pos = value_to_face_offset(first_x, x_axis, face_width);
-    let first_y_pos = -value_to_face_offset(first_y, y_axis, face_height);
-    d.push(node::element::path::Command::Move(
-        node::element::path::Position::Absolute,
-        (first_x_pos, first_y_pos).into(),
-    ));
-    for &(x, y) in s {
-        let x_pos = value_to_face_offset(x, x_axis, face_width);
-        let y_pos = -value_to_face_offset(y, y_axis, face_height);
-        d.push(node::element::path::Command::Line(
-            node::element::path::Position::Absolute,
-            (x_pos, y_pos).into(),
-        ));
+/// Set the opacity of a fill, where it is not solid
+fn set_fill_style<T: Node>(mut node: T, style: &style::BoxStyle) -> T {
+    if style.get_opacity() < 1. {
+        node.assign("fill-opacity", style.get_opacity());
     }
-
-    let path = node::element::path::Data::from(d);
-
-    group.append(
-        node::element::Path::new()
-            .set("fill", "none")
-            .set("stroke", style.get_colour())
-            .set("stroke-width", style.get_width())
-            .set(
-                "stroke-linejoin",
-                match style.get_linejoin() {
-                    style::LineJoin::Miter => "miter",
-                    style::LineJoin::Round => "round",
-                },
-            )
-            .set("d", path),
-    );
-
-    group
+    node
 }
 
-pub fn draw_face_boxplot<L>(
-    d: &[f64],
-    label: &L,
-    x_axis: &axis::CategoricalAxis,
-    y_axis: &axis::ContinuousAxis,
-    face_width: f64,
-    face_height: f64,
-    style: &style::BoxStyle,
-) -> node::element::Group
-where
-    L: Into<String>,
-    String: std::cmp::PartialEq<L>,
-{
-    let mut group = node::element::Group::new();
-
-    let tick_index = x_axis.ticks().iter().position(|t| t == label).unwrap(); // TODO this should raise an error
-    let space_per_tick = face_width / x_axis.ticks().len() as f64;
-    let tick_pos = (tick_index as f64 * space_per_tick) + (0.5 * space_per_tick);
-
-    let box_width = space_per_tick / 2.;
-
-    let (q1, median, q3) = utils::quartiles(d);
-
-    let box_start = -value_to_face_offset(q3, y_axis, face_height);
-    let box_end = -value_to_face_offset(q1, y_axis, face_height);
-
-    group.append(
-        node::element::Rectangle::new()
-            .set("x", tick_pos - (box_width / 2.))
-            .set("y", box_start)
-            .set("width", box_width)
-            .set("height", box_end - box_start)
-            .set("fill", style.get_fill())
-            .set("stroke", "black"),
-    );
-
-    let mid_line = -value_to_face_offset(median, y_axis, face_height);
-
-    group.append(
-        node::element::Line::new()
-            .set("x1", tick_pos - (box_width / 2.))
-            .set("y1", mid_line)
-            .set("x2", tick_pos + (box_width / 2.))
-            .set("y2", mid_line)
-            .set("stroke", "black"),
-    );
-
-    let (min, max) = utils::range(d);
-
-    let whisker_bottom = -value_to_face_offset(min, y_axis, face_height);
-    let whisker_top = -value_to_face_offset(max, y_axis, face_height);
-
-    group.append(
-        node::element::Line::new()
-            .set("x1", tick_pos)
-            .set("y1", whisker_bottom)
-            .set("x2", tick_pos)
-            .set("y2", box_end)
-            .set("stroke", "black"),
-    );
-
-    group.append(
-        node::element::Line::new()
-            .set("x1", tick_pos)
-            .set("y1", whisker_top)
-            .set("x2", tick_pos)
-            .set("y2", box_start)
-            .set("stroke", "black"),
-    );
-
-    group
+/// A text element with its anchor at `position` on the face
+fn text_element(
+    position: (f64, f64),
+    text: &str,
+    anchor: TextAnchor,
+    font_size: f64,
+) -> node::element::Text {
+    node::element::Text::new()
+        .set("x", position.0)
+        .set("y", -position.1)
+        .set(
+            "text-anchor",
+            match anchor {
+                TextAnchor::Start => "start",
+                TextAnchor::Middle => "middle",
+                TextAnchor::End => "end",
+            },
+        )
+        .set("font-size", font_size)
+        .add(node::Text::new(text))
 }
 
-pub fn draw_face_barchart<L>(
-    d: f64,
-    label: &L,
-    x_axis: &axis::CategoricalAxis,
-    y_axis: &axis::ContinuousAxis,
-    face_width: f64,
-    face_height: f64,
-    style: &style::BoxStyle,
-) -> node::element::Group
-where
-    L: Into<String>,
-    String: std::cmp::PartialEq<L>,
-{
-    let mut group = node::element::Group::new();
+/// How many surfaces have been drawn on outside of a page, to keep their clip ids apart
+static STANDALONE_SURFACES: AtomicUsize = AtomicUsize::new(0);
+
+/// A surface which draws onto an SVG group, with the origin at the bottom-left corner of the face
+pub(crate) struct SvgSurface {
+    size: (f64, f64),
+    /// The stack of groups being drawn into, one more for each clip region
+    groups: Vec<node::element::Group>,
+    /// The start of the id of each clip region, which is followed by its number
+    clip_prefix: String,
+    /// The number given to the next clip region, which must be unique within the document
+    next_clip_id: usize,
+}
 
-    let tick_index = x_axis.ticks().iter().position(|t| t == label).unwrap(); // TODO this should raise an error
-    let space_per_tick = face_width / x_axis.ticks().len() as f64;
-    let tick_pos = (tick_index as f64 * space_per_tick) + (0.5 * space_per_tick);
+impl SvgSurface {
+    /// A surface whose group may be put into any document.
+    ///
+    /// Its clip ids are different from those of every other surface made this way,
+    /// and from those of a page, so that groups drawn separately can be put together.
+    pub(crate) fn new(face_width: f64, face_height: f64) -> Self {
+        let surface = STANDALONE_SURFACES.fetch_add(1, Ordering::Relaxed);
+        SvgSurface {
+            clip_prefix: format!("plotlib-clip-{}-", surface),
+            ..SvgSurface::with_clip_ids(face_width, face_height, 0)
+        }
+    }
 
-    let box_width = space_per_tick / 2.;
+    /// A surface for one part of a larger document, numbering its clip regions from `first_clip_id`
+    pub(crate) fn with_clip_ids(face_width: f64, face_height: f64, first_clip_id: usize) -> Self {
+        SvgSurface {
+            size: (face_width, face_height),
+            groups: vec![node::element::Group::new()],
+            clip_prefix: String::from("plotlib-clip-"),
+            next_clip_id: first_clip_id,
+        }
+    }
 
-    let box_start = -value_to_face_offset(d, y_axis, face_height);
-    let box_end = -value_to_face_offset(0.0, y_axis, face_height);
+    /// The first clip id not used by this surface, for the next part of the document to carry on from
+    pub(crate) fn next_clip_id(&self) -> usize {
+        self.next_clip_id
+    }
 
-    group.append(
-        node::element::Rectangle::new()
-            .set("x", tick_pos - (box_width / 2.))
-            .set("y", box_start)
-            .set("width", box_width)
-            .set("height", box_end - box_start)
-            .set("fill", style.get_fill())
-            .set("stroke", "black"),
-    );
+    /// Finish drawing and return everything that was drawn
+    pub(crate) fn into_group(mut self) -> node::element::Group {
+        while self.groups.len() > 1 {
+            self.pop_clip();
+        }
+        self.groups.pop().unwrap()
+    }
 
-    group
+    /// Add an element which has already been drawn
+    pub(crate) fn append<T: Node>(&mut self, node: T) {
+        self.groups.last_mut().unwrap().append(node);
+    }
 }
 
-pub(crate) fn draw_grid(grid: GridType, face_width: f64, face_height: f64) -> node::element::Group {
-    match grid {
-        GridType::HorizontalOnly(grid) => {
-            let (ymin, ymax) = (0f64, face_height);
-            let y_step = (ymax - ymin) / f64::from(grid.ny);
-            let mut lines = node::element::Group::new();
+impl Surface for SvgSurface {
+    fn size(&self) -> (f64, f64) {
+        self.size
+    }
 
-            for iy in 0..=grid.ny {
-                let y = f64::from(iy) * y_step + ymin;
-                let line = horizontal_line(-y, 0.0, face_width, grid.color.as_str());
-                lines = lines.add(line);
-            }
+    fn line(&mut self, start: (f64, f64), end: (f64, f64), style: &style::LineStyle) {
+        self.append(set_stroke_style(
+            node::element::Line::new()
+                .set("x1", start.0)
+                .set("y1", -start.1)
+                .set("x2", end.0)
+                .set("y2", -end.1)
+                .set("stroke", style.get_colour())
+                .set("stroke-width", style.get_width()),
+            style,
+        ));
+    }
 
-            lines
+    fn path(&mut self, points: &[(f64, f64)], style: &style::LineStyle) {
+        let mut d: Vec<node::element::path::Command> = vec![];
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let position = (x, -y).into();
+            d.push(if i == 0 {
+                node::element::path::Command::Move(node::element::path::Position::Absolute, position)
+            } else {
+                node::element::path::Command::Line(node::element::path::Position::Absolute, position)
+            });
+        }
+        if d.is_empty() {
+            return;
         }
-        GridType::Both(grid) => {
-            let (xmin, xmax) = (0f64, face_width);
-            let (ymin, ymax) = (0f64, face_height);
 
-            let x_step = (xmax - xmin) / f64::from(grid.nx);
-            let y_step = (ymax - ymin) / f64::from(grid.ny);
+        let path = node::element::path::Data::from(d);
+
+        self.append(set_stroke_style(
+            node::element::Path::new()
+                .set("fill", "none")
+                .set("stroke", style.get_colour())
+                .set("stroke-width", style.get_width())
+                .set(
+                    "stroke-linejoin",
+                    match style.get_linejoin() {
+                        style::LineJoin::Miter => "miter",
+                        style::LineJoin::Round => "round",
+                    },
+                )
+                .set("d", path),
+            style,
+        ));
+    }
+
+    fn rect(&mut self, origin: (f64, f64), size: (f64, f64), style: &style::BoxStyle) {
+        let (x, width) = if size.0 < 0. {
+            (origin.0 + size.0, -size.0)
+        } else {
+            (origin.0, size.0)
+        };
+        let (y, height) = if size.1 < 0. {
+            (origin.1 + size.1, -size.1)
+        } else {
+            (origin.1, size.1)
+        };
+        self.append(set_fill_style(
+            node::element::Rectangle::new()
+                .set("x", x)
+                .set("y", -(y + height))
+                .set("width", width)
+                .set("height", height)
+                .set("fill", style.get_fill())
+                .set("stroke", style.get_outline()),
+            style,
+        ));
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], style: &style::BoxStyle) {
+        let corners: Vec<String> = points.iter().map(|&(x, y)| format!("{},{}", x, -y)).collect();
+        self.append(set_fill_style(
+            node::element::Polygon::new()
+                .set("points", corners.join(" "))
+                .set("fill", style.get_fill())
+                .set("stroke", style.get_outline()),
+            style,
+        ));
+    }
 
-            let mut lines = node::element::Group::new();
+    fn text(&mut self, position: (f64, f64), text: &str, anchor: TextAnchor, font_size: f64) {
+        self.append(text_element(position, text, anchor, font_size));
+    }
 
-            for iy in 0..=grid.ny {
-                let y = f64::from(iy) * y_step + ymin;
-                let line = horizontal_line(-y, 0.0, face_width, grid.color.as_str());
-                lines = lines.add(line);
-            }
+    fn marker(&mut self, position: (f64, f64), style: &style::PointStyle) {
+        self.append(draw_marker(position.0, -position.1, style));
+    }
 
-            for ix in 0..=grid.nx {
-                let x = f64::from(ix) * x_step + xmin;
-                let line = vertical_line(x, 0.0, -face_height, grid.color.as_str());
-                lines = lines.add(line);
-            }
+    fn push_clip(&mut self, origin: (f64, f64), size: (f64, f64)) {
+        let id = format!("{}{}", self.clip_prefix, self.next_clip_id);
+        self.next_clip_id += 1;
+        let clip_path = node::element::ClipPath::new().set("id", id.as_str()).add(
+            node::element::Rectangle::new()
+                .set("x", origin.0)
+                .set("y", -(origin.1 + size.1))
+                .set("width", size.0)
+                .set("height", size.1),
+        );
+        self.append(clip_path);
+        self.groups
+            .push(node::element::Group::new().set("clip-path", format!("url(#{})", id)));
+    }
 
-            lines
+    fn pop_clip(&mut self) {
+        if self.groups.len() > 1 {
+            let group = self.groups.pop().unwrap();
+            self.append(group);
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_value_to_face_offset() {
-        let axis = axis::ContinuousAxis::new(-2., 5., 6);
-        assert_eq!(value_to_face_offset(-2.0, &axis, 14.0), 0.0);
-        assert_eq!(value_to_face_offset(5.0, &axis, 14.0), 14.0);
-        assert_eq!(value_to_face_offset(0.0, &axis, 14.0), 4.0);
-        assert_eq!(value_to_face_offset(-4.0, &axis, 14.0), -4.0);
-        assert_eq!(value_to_face_offset(7.0, &axis, 14.0), 18.0);
+    fn rotated_text(
+        &mut self,
+        position: (f64, f64),
+        text: &str,
+        anchor: TextAnchor,
+        font_size: f64,
+        angle: f64,
+    ) {
+        // SVG turns clockwise, as its y-axis points down the page
+        self.append(text_element(position, text, anchor, font_size).set(
+            "transform",
+            format!("rotate({} {} {})", -angle, position.0, -position.1),
+        ));
     }
 }
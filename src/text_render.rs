@@ -1,17 +1,22 @@
 //! A module for plotting graphs
 
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::axis;
+use crate::grid::Grid;
+use crate::legend::Legend;
+use crate::render::{self, AxisSide, LegendEntry, Surface, TextAnchor};
 use crate::repr;
 use crate::style;
+use crate::utils;
 use crate::utils::PairWise;
 
 // Given a value like a tick label or a bin count,
 // calculate how far from the x-axis it should be plotted
 fn value_to_axis_cell_offset(value: f64, axis: &axis::ContinuousAxis, face_cells: u32) -> i32 {
-    let data_per_cell = (axis.max() - axis.min()) / f64::from(face_cells);
-    ((value - axis.min()) / data_per_cell).round() as i32
+    axis.value_to_face_offset(value, f64::from(face_cells))
+        .round() as i32
 }
 
 /// Given a list of ticks to display,
@@ -25,6 +30,16 @@ fn tick_offset_map(axis: &axis::ContinuousAxis, face_width: u32) -> HashMap<i32,
         .collect()
 }
 
+/// The cells which hold a minor tick, and not a major one
+fn minor_tick_cells(axis: &axis::ContinuousAxis, face_width: u32) -> Vec<i32> {
+    let major = tick_offset_map(axis, face_width);
+    axis.get_minor_ticks()
+        .iter()
+        .map(|&tick| value_to_axis_cell_offset(tick, axis, face_width))
+        .filter(|cell| !major.contains_key(cell))
+        .collect()
+}
+
 /// Given a histogram object,
 /// the total scale of the axis
 /// and the number of face cells to work with,
@@ -116,48 +131,105 @@ impl XAxisLabel {
     }
 }
 
-fn create_x_axis_labels(x_tick_map: &HashMap<i32, f64>) -> Vec<XAxisLabel> {
-    let mut ls: Vec<_> = x_tick_map
+fn create_x_axis_labels(
+    x_axis: &axis::ContinuousAxis,
+    x_tick_map: &HashMap<i32, f64>,
+) -> Vec<XAxisLabel> {
+    x_tick_map
         .iter()
         .map(|(&offset, &tick)| XAxisLabel {
-            text: tick.to_string(),
+            text: x_axis.tick_label(tick),
             offset,
         })
-        .collect();
-    ls.sort_by_key(|l| l.offset);
-    ls
+        .collect()
+}
+
+/// A label for each category, centred in the cells given to it
+fn category_labels(axis: &axis::CategoricalAxis, face_cells: u32) -> Vec<XAxisLabel> {
+    let space_per_tick = axis.category_width(f64::from(face_cells));
+    axis.ticks()
+        .iter()
+        .enumerate()
+        .map(|(i, tick)| XAxisLabel {
+            text: tick.clone(),
+            offset: ((i as f64 + 0.5) * space_per_tick).round() as i32,
+        })
+        .collect()
 }
 
 pub fn render_y_axis_strings(y_axis: &axis::ContinuousAxis, face_height: u32) -> (String, i32) {
-    // Get the strings and offsets we'll use for the y-axis
-    let y_tick_map = tick_offset_map(y_axis, face_height);
+    render_vertical_axis_strings(y_axis, face_height, false)
+}
 
-    // Find a minimum size for the left gutter
-    let longest_y_label_width = y_tick_map
-        .values()
-        .map(|n| n.to_string().len())
-        .max()
-        .expect("ERROR: There are no y-axis ticks");
-
-    let y_axis_label = format!(
-        "{: ^width$}",
+/// Render a y-axis for the right-hand side of a face, mirroring `render_y_axis_strings`.
+///
+/// The axis line itself is in the first column of the string.
+pub fn render_y2_axis_strings(y_axis: &axis::ContinuousAxis, face_height: u32) -> (String, i32) {
+    render_vertical_axis_strings(y_axis, face_height, true)
+}
+
+fn render_vertical_axis_strings(
+    y_axis: &axis::ContinuousAxis,
+    face_height: u32,
+    right: bool,
+) -> (String, i32) {
+    let y_tick_labels: HashMap<_, _> = tick_offset_map(y_axis, face_height)
+        .into_iter()
+        .map(|(offset, tick)| (offset, y_axis.tick_label(tick)))
+        .collect();
+    let y_minor_ticks = minor_tick_cells(y_axis, face_height);
+    render_vertical_axis(
+        &y_tick_labels,
+        &y_minor_ticks,
         y_axis.get_label(),
-        width = face_height as usize + 1
-    );
+        face_height,
+        right,
+    )
+}
+
+/// Render a categorical axis up the side of a face, with the first category at the top.
+///
+/// Like `render_y_axis_strings`, this also gives the width of the longest category label.
+pub(crate) fn render_categorical_y_axis_strings(
+    axis: &axis::CategoricalAxis,
+    face_height: u32,
+    right: bool,
+) -> (String, i32) {
+    // The categories are laid out from the top, but the rows are counted from the bottom
+    let labels: HashMap<_, _> = category_labels(axis, face_height)
+        .into_iter()
+        .map(|label| (face_height as i32 - label.offset, label.text))
+        .collect();
+    render_vertical_axis(&labels, &[], axis.get_label(), face_height, right)
+}
+
+/// Render a vertical axis with the given tick labels and minor ticks, keyed by their row
+fn render_vertical_axis(
+    y_tick_labels: &HashMap<i32, String>,
+    y_minor_ticks: &[i32],
+    label: &str,
+    face_height: u32,
+    right: bool,
+) -> (String, i32) {
+    // Find a minimum size for the left gutter
+    let longest_y_label_width = y_tick_labels.values().map(|l| l.len()).max().unwrap_or(0);
+
+    let y_axis_label = format!("{: ^width$}", label, width = face_height as usize + 1);
     let y_axis_label: Vec<_> = y_axis_label.chars().rev().collect();
 
     // Generate a list of strings to label the y-axis
     let y_label_strings: Vec<_> = (0..=face_height)
-        .map(|line| match y_tick_map.get(&(line as i32)) {
-            Some(v) => v.to_string(),
+        .map(|line| match y_tick_labels.get(&(line as i32)) {
+            Some(l) => l.clone(),
             None => "".to_string(),
         })
         .collect();
 
     // Generate a list of strings to tick the y-axis
     let y_tick_strings: Vec<_> = (0..=face_height)
-        .map(|line| match y_tick_map.get(&(line as i32)) {
+        .map(|line| match y_tick_labels.get(&(line as i32)) {
             Some(_) => "-".to_string(),
+            None if y_minor_ticks.contains(&(line as i32)) => "·".to_string(),
             None => " ".to_string(),
         })
         .collect();
@@ -179,14 +251,25 @@ pub fn render_y_axis_strings(y_axis: &axis::ContinuousAxis, face_height: u32) ->
     let axis_string: Vec<String> = iter
         .rev()
         .map(|(l, ls, t, a)| {
-            format!(
-                "{} {:>num_width$}{}{}",
-                l,
-                ls,
-                t,
-                a,
-                num_width = longest_y_label_width
-            )
+            if right {
+                format!(
+                    "{}{} {:<num_width$} {}",
+                    a,
+                    t,
+                    ls,
+                    l,
+                    num_width = longest_y_label_width
+                )
+            } else {
+                format!(
+                    "{} {:>num_width$}{}{}",
+                    l,
+                    ls,
+                    t,
+                    a,
+                    num_width = longest_y_label_width
+                )
+            }
         })
         .collect();
 
@@ -198,22 +281,50 @@ pub fn render_y_axis_strings(y_axis: &axis::ContinuousAxis, face_height: u32) ->
 pub fn render_x_axis_strings(x_axis: &axis::ContinuousAxis, face_width: u32) -> (String, i32) {
     // Get the strings and offsets we'll use for the x-axis
     let x_tick_map = tick_offset_map(x_axis, face_width as u32);
+    let x_labels = create_x_axis_labels(x_axis, &x_tick_map);
+    let x_minor_ticks = minor_tick_cells(x_axis, face_width);
+    render_horizontal_axis(x_labels, &x_minor_ticks, x_axis.get_label(), face_width)
+}
+
+/// Render a categorical axis along the bottom of a face.
+///
+/// Like `render_x_axis_strings`, this also gives where the labels start relative to the axis.
+pub(crate) fn render_categorical_x_axis_strings(
+    axis: &axis::CategoricalAxis,
+    face_width: u32,
+) -> (String, i32) {
+    let labels = category_labels(axis, face_width);
+    render_horizontal_axis(labels, &[], axis.get_label(), face_width)
+}
+
+/// Render a horizontal axis with a tick at each of the labels and at each of the minor ticks
+fn render_horizontal_axis(
+    mut x_labels: Vec<XAxisLabel>,
+    x_minor_ticks: &[i32],
+    label: &str,
+    face_width: u32,
+) -> (String, i32) {
+    x_labels.sort_by_key(|l| l.offset);
 
     // Create a string which will be printed to give the x-axis tick marks
     let x_axis_tick_string: String = (0..=face_width)
-        .map(|cell| match x_tick_map.get(&(cell as i32)) {
-            Some(_) => '|',
-            None => ' ',
+        .map(|cell| {
+            if x_labels.iter().any(|l| l.offset == cell as i32) {
+                '|'
+            } else if x_minor_ticks.contains(&(cell as i32)) {
+                '\''
+            } else {
+                ' '
+            }
         })
         .collect();
 
     // Create a string which will be printed to give the x-axis labels
-    let x_labels = create_x_axis_labels(&x_tick_map);
     let start_offset = x_labels
         .iter()
         .map(|label| label.start_offset())
         .min()
-        .expect("ERROR: Could not compute start offset of x-axis");
+        .unwrap_or(0);
 
     // This string will be printed, starting at start_offset relative to the x-axis zero cell
     let mut x_axis_label_string = "".to_string();
@@ -239,11 +350,7 @@ pub fn render_x_axis_strings(x_axis: &axis::ContinuousAxis, face_width: u32) ->
         .chain(std::iter::repeat('-').take(face_width as usize))
         .collect();
 
-    let x_axis_label = format!(
-        "{: ^width$}",
-        x_axis.get_label(),
-        width = face_width as usize
-    );
+    let x_axis_label = format!("{: ^width$}", label, width = face_width as usize);
 
     let x_axis_string = if start_offset.is_positive() {
         let padding = (0..start_offset).map(|_| " ").collect::<String>();
@@ -344,29 +451,9 @@ pub fn render_face_bars(
     face_strings.join("\n")
 }
 
-/// Given a scatter plot,
-/// the x ands y-axes
-/// and the face height and width,
-/// create the strings to be drawn as the face
-pub fn render_face_points(
-    s: &[(f64, f64)],
-    x_axis: &axis::ContinuousAxis,
-    y_axis: &axis::ContinuousAxis,
-    face_width: u32,
-    face_height: u32,
-    style: &style::PointStyle,
-) -> String {
-    let points: Vec<_> = s
-        .iter()
-        .map(|&(x, y)| {
-            (
-                value_to_axis_cell_offset(x, x_axis, face_width),
-                value_to_axis_cell_offset(y, y_axis, face_height),
-            )
-        })
-        .collect();
-
-    let marker = match style.get_marker() {
+/// The character used to draw a point marker
+fn marker_char(marker: &style::PointMarker) -> char {
+    match *marker {
         style::PointMarker::Circle => '●',
         style::PointMarker::Square => '■',
         style::PointMarker::Cross => '×',
@@ -375,32 +462,388 @@ pub fn render_face_points(
         style::PointMarker::Triangle => '▲',
         style::PointMarker::TriangleDown => '▼',
         style::PointMarker::Diamond => '♦',
+    }
+}
+
+/// The character which best matches the direction of a line
+fn line_char(dx: f64, dy: f64) -> char {
+    if dy.abs() <= dx.abs() / 2. {
+        '-'
+    } else if dx.abs() <= dy.abs() / 2. {
+        '|'
+    } else if dx.signum() == dy.signum() {
+        '/'
+    } else {
+        '\\'
+    }
+}
+
+/// The axes and title drawn around a face, as rendered strings to lay out around it
+#[derive(Default)]
+struct TextFrame {
+    /// The axis up the left-hand side, with the width of its longest tick label
+    left: Option<(String, i32)>,
+    /// The axis along the bottom, with where its tick labels start relative to the axis line
+    bottom: Option<(String, i32)>,
+    /// The axis up the right-hand side, with the width of its longest tick label
+    right: Option<(String, i32)>,
+    title: Option<String>,
+    /// The lines of the legend, listed beneath the axes
+    legend: Vec<String>,
+}
+
+impl TextFrame {
+    fn is_empty(&self) -> bool {
+        self.left.is_none()
+            && self.bottom.is_none()
+            && self.right.is_none()
+            && self.title.is_none()
+            && self.legend.is_empty()
+    }
+
+    /// Lay out the axes and title around a face, leaving enough space for each of them
+    fn around(&self, face: &str, face_width: u32, face_height: u32) -> String {
+        let longest_left_label_width = self.left.as_ref().map_or(0, |&(_, width)| width);
+        let start_offset = self.bottom.as_ref().map_or(0, |&(_, offset)| offset);
+
+        let left_gutter_width =
+            std::cmp::max(longest_left_label_width + 3, start_offset.wrapping_neg()) as u32;
+        let right_gutter_width = match self.right {
+            Some((_, longest_right_label_width)) => longest_right_label_width as u32 + 4,
+            None => 0,
+        };
+
+        let view_width = face_width + 1 + left_gutter_width + 1 + right_gutter_width;
+        let view_height = face_height + 4;
+
+        let mut view_string = empty_face(view_width, view_height);
+        view_string = overlay(&view_string, face, left_gutter_width as i32 + 1, 0);
+        if let Some((ref axis_string, _)) = self.left {
+            view_string = overlay(
+                &view_string,
+                axis_string,
+                left_gutter_width as i32 - 2 - longest_left_label_width,
+                0,
+            );
+        }
+        if let Some((ref axis_string, _)) = self.bottom {
+            view_string = overlay(
+                &view_string,
+                axis_string,
+                left_gutter_width as i32,
+                face_height as i32,
+            );
+        }
+        if let Some((ref axis_string, _)) = self.right {
+            view_string = overlay(
+                &view_string,
+                axis_string,
+                (left_gutter_width + 1 + face_width) as i32,
+                0,
+            );
+        }
+
+        if let Some(ref title) = self.title {
+            let title_line = format!(
+                "{}{}",
+                " ".repeat(left_gutter_width as usize + 1),
+                centred_line(title, face_width as usize)
+            );
+            view_string = format!("{}\n{}", title_line, view_string);
+        }
+
+        for line in &self.legend {
+            let line = format!("{}{}", " ".repeat(left_gutter_width as usize + 1), line);
+            view_string = format!("{}\n{:<2$}", view_string, line, view_width as usize);
+        }
+
+        view_string
+    }
+}
+
+/// The key and label of a legend entry as a line of text,
+/// with the key three characters wide so that the labels line up
+fn legend_line(entry: &LegendEntry) -> String {
+    let key = match (&entry.point_style, &entry.line_style, &entry.box_style) {
+        (Some(point_style), Some(_), _) => format!("-{}-", marker_char(&point_style.get_marker())),
+        (Some(point_style), None, _) => format!(" {} ", marker_char(&point_style.get_marker())),
+        (None, Some(_), _) => "---".to_string(),
+        (None, None, Some(_)) => "[ ]".to_string(),
+        (None, None, None) => "   ".to_string(),
     };
+    format!("{} {}", key, entry.label)
+}
 
-    let mut face_strings: Vec<String> = vec![];
-    for line in 1..=face_height {
-        let mut line_string = String::new();
-        for column in 1..=face_width as usize {
-            line_string.push(if points.contains(&(column as i32, line as i32)) {
-                marker
+/// A surface which draws onto a grid of characters, one unit per cell.
+///
+/// Cells are numbered from 1 in each direction
+/// so that a value on the axis line itself is not drawn on the face.
+/// Any axes and title drawn onto it are laid out around the face when it is shown.
+pub(crate) struct TextSurface {
+    width: u32,
+    height: u32,
+    /// The rows of cells, from the top of the face down
+    cells: Vec<Vec<char>>,
+    /// The stack of clip regions as (min column, min row, max column, max row)
+    clips: Vec<(i32, i32, i32, i32)>,
+    frame: TextFrame,
+}
+
+impl TextSurface {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        TextSurface {
+            width,
+            height,
+            cells: vec![vec![' '; width as usize]; height as usize],
+            clips: vec![],
+            frame: TextFrame::default(),
+        }
+    }
+
+    fn cell(position: (f64, f64)) -> (i32, i32) {
+        (position.0.round() as i32, position.1.round() as i32)
+    }
+
+    /// The cells which can be drawn on, within the face and every clip region,
+    /// as (min column, min row, max column, max row)
+    fn visible_cells(&self) -> (i32, i32, i32, i32) {
+        self.clips.iter().fold(
+            (1, 1, self.width as i32, self.height as i32),
+            |(min_column, min_row, max_column, max_row), &(left, bottom, right, top)| {
+                (
+                    min_column.max(left),
+                    min_row.max(bottom),
+                    max_column.min(right),
+                    max_row.min(top),
+                )
+            },
+        )
+    }
+
+    fn set(&mut self, (column, row): (i32, i32), c: char) {
+        let (min_column, min_row, max_column, max_row) = self.visible_cells();
+        if column >= min_column && column <= max_column && row >= min_row && row <= max_row {
+            self.cells[(self.height as i32 - row) as usize][(column - 1) as usize] = c;
+        }
+    }
+}
+
+impl fmt::Display for TextSurface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self.cells.iter().map(|l| l.iter().collect()).collect();
+        let face = lines.join("\n");
+        if self.frame.is_empty() {
+            write!(f, "{}", face)
+        } else {
+            write!(f, "{}", self.frame.around(&face, self.width, self.height))
+        }
+    }
+}
+
+impl Surface for TextSurface {
+    fn size(&self) -> (f64, f64) {
+        (f64::from(self.width), f64::from(self.height))
+    }
+
+    fn line(&mut self, start: (f64, f64), end: (f64, f64), _style: &style::LineStyle) {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let c = line_char(dx, dy);
+        // Cut the line at the edge of the visible cells first,
+        // so that only the part which can be seen is stepped along
+        let (min_column, min_row, max_column, max_row) = self.visible_cells();
+        let (start, end) = match utils::clip_segment(
+            start,
+            end,
+            (f64::from(min_column) - 0.5, f64::from(min_row) - 0.5),
+            (f64::from(max_column) + 0.5, f64::from(max_row) + 0.5),
+        ) {
+            Some(segment) => segment,
+            None => return,
+        };
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        // Sample at least twice per cell so that no cell along the line is skipped
+        let steps = (2. * dx.abs().max(dy.abs())).ceil().max(1.) as u32;
+        for step in 0..=steps {
+            let t = f64::from(step) / f64::from(steps);
+            self.set(Self::cell((start.0 + t * dx, start.1 + t * dy)), c);
+        }
+    }
+
+    fn path(&mut self, points: &[(f64, f64)], style: &style::LineStyle) {
+        for (&start, &end) in points.pairwise() {
+            self.line(start, end, style);
+        }
+    }
+
+    fn rect(&mut self, origin: (f64, f64), size: (f64, f64), _style: &style::BoxStyle) {
+        let (x1, y1) = Self::cell(origin);
+        let (x2, y2) = Self::cell((origin.0 + size.0, origin.1 + size.1));
+        let (left, right) = (x1.min(x2), x1.max(x2));
+        let (bottom, top) = (y1.min(y2), y1.max(y2));
+        // Only the edges which can be seen are walked along
+        let (min_column, min_row, max_column, max_row) = self.visible_cells();
+        for row in bottom.max(min_row)..=top.min(max_row) {
+            self.set((left, row), '|');
+            self.set((right, row), '|');
+        }
+        for column in left.max(min_column)..=right.min(max_column) {
+            self.set((column, bottom), '-');
+            self.set((column, top), '-');
+        }
+    }
+
+    /// The inside of the shape is shaded, leaving the edges to be drawn as lines
+    fn polygon(&mut self, points: &[(f64, f64)], _style: &style::BoxStyle) {
+        for row in 1..=self.height as i32 {
+            for column in 1..=self.width as i32 {
+                if utils::point_in_polygon((f64::from(column), f64::from(row)), points) {
+                    self.set((column, row), '░');
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, position: (f64, f64), text: &str, anchor: TextAnchor, _font_size: f64) {
+        let (column, row) = Self::cell(position);
+        let length = text.chars().count() as i32;
+        let start = match anchor {
+            TextAnchor::Start => column,
+            TextAnchor::Middle => column - length / 2,
+            TextAnchor::End => column - length + 1,
+        };
+        for (i, c) in text.chars().enumerate() {
+            self.set((start + i as i32, row), c);
+        }
+    }
+
+    fn marker(&mut self, position: (f64, f64), style: &style::PointStyle) {
+        self.set(Self::cell(position), marker_char(&style.get_marker()));
+    }
+
+    fn push_clip(&mut self, origin: (f64, f64), size: (f64, f64)) {
+        let (x1, y1) = Self::cell(origin);
+        let (x2, y2) = Self::cell((origin.0 + size.0, origin.1 + size.1));
+        self.clips
+            .push((x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)));
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    fn is_text(&self) -> bool {
+        true
+    }
+
+    fn axis(&mut self, axis: &axis::ContinuousAxis, side: AxisSide) {
+        match side {
+            AxisSide::Bottom => self.frame.bottom = Some(render_x_axis_strings(axis, self.width)),
+            AxisSide::Left => self.frame.left = Some(render_y_axis_strings(axis, self.height)),
+            AxisSide::Right => self.frame.right = Some(render_y2_axis_strings(axis, self.height)),
+        }
+    }
+
+    fn categorical_axis(&mut self, axis: &axis::CategoricalAxis, side: AxisSide) {
+        match side {
+            AxisSide::Bottom => {
+                self.frame.bottom = Some(render_categorical_x_axis_strings(axis, self.width))
+            }
+            AxisSide::Left => {
+                self.frame.left = Some(render_categorical_y_axis_strings(axis, self.height, false))
+            }
+            AxisSide::Right => {
+                self.frame.right = Some(render_categorical_y_axis_strings(axis, self.height, true))
+            }
+        }
+    }
+
+    /// The lines are drawn with box-drawing characters, with a cross where they meet
+    fn grid(
+        &mut self,
+        grid: &Grid,
+        x_axis: Option<&axis::ContinuousAxis>,
+        y_axis: &axis::ContinuousAxis,
+    ) {
+        let rows = render_grid(grid, x_axis, y_axis, self.width, self.height);
+        render::draw_face_characters(&rows, self);
+    }
+
+    /// There is no room for a legend on a text face, so it is listed beneath the axes instead,
+    /// one entry to a line, wherever it is placed
+    fn legend(&mut self, legend: &Legend, entries: &[LegendEntry], _top_left: (f64, f64)) {
+        self.frame.legend = legend
+            .get_title()
+            .map(str::to_string)
+            .into_iter()
+            .chain(entries.iter().map(legend_line))
+            .collect();
+    }
+
+    fn title(&mut self, title: &str) {
+        self.frame.title = Some(title.to_string());
+    }
+}
+
+/// Draw the lines of a grid across a face with light box-drawing characters,
+/// dashed for the lines at minor ticks
+pub(crate) fn render_grid(
+    grid: &Grid,
+    x_axis: Option<&axis::ContinuousAxis>,
+    y_axis: &axis::ContinuousAxis,
+    face_width: u32,
+    face_height: u32,
+) -> String {
+    let mut cells = vec![vec![' '; face_width as usize]; face_height as usize];
+    let lines = render::grid_lines(
+        grid,
+        x_axis,
+        y_axis,
+        f64::from(face_width),
+        f64::from(face_height),
+    );
+    for line in lines {
+        // As on the face, cells are numbered from 1 so lines on the axes themselves are not drawn
+        let offset = line.offset.round() as i32;
+        let (length, c) = match (line.vertical, line.major) {
+            (true, true) => (face_height, '│'),
+            (true, false) => (face_height, '┊'),
+            (false, true) => (face_width, '─'),
+            (false, false) => (face_width, '┈'),
+        };
+        let limit = if line.vertical {
+            face_width
+        } else {
+            face_height
+        };
+        if offset < 1 || offset > limit as i32 {
+            continue;
+        }
+        for along in 0..length as usize {
+            let (row, column) = if line.vertical {
+                (along, offset as usize - 1)
             } else {
-                ' '
-            });
+                (face_height as usize - offset as usize, along)
+            };
+            let cell = &mut cells[row][column];
+            *cell = match *cell {
+                ' ' => c,
+                existing if existing == c => c,
+                _ => '┼',
+            };
         }
-        face_strings.push(line_string);
     }
-    let face_strings: Vec<String> = face_strings.iter().rev().cloned().collect();
-    face_strings.join("\n")
+    let rows: Vec<String> = cells.iter().map(|row| row.iter().collect()).collect();
+    rows.join("\n")
 }
 
 /// Given two 'rectangular' strings, overlay the second on the first offset by `x` and `y`
 pub fn overlay(under: &str, over: &str, x: i32, y: i32) -> String {
     let split_under: Vec<_> = under.split('\n').collect();
-    let under_width = split_under.iter().map(|s| s.len()).max().unwrap();
+    let under_width = split_under.iter().map(|s| s.chars().count()).max().unwrap();
     let under_height = split_under.len();
 
     let split_over: Vec<String> = over.split('\n').map(|s| s.to_string()).collect();
-    let over_width = split_over.iter().map(|s| s.len()).max().unwrap();
+    let over_width = split_over.iter().map(|s| s.chars().count()).max().unwrap();
 
     // Take `over` and pad it so that it matches `under`'s dimensions
 
@@ -432,7 +875,7 @@ pub fn overlay(under: &str, over: &str, x: i32, y: i32) -> String {
     };
 
     // pad out end of vector
-    let over_width = split_over.iter().map(|s| s.len()).max().unwrap();
+    let over_width = split_over.iter().map(|s| s.chars().count()).max().unwrap();
     let over_height = split_over.len();
     let lines_deficit = under_height as i32 - over_height as i32;
     let split_over: Vec<String> = if lines_deficit.is_positive() {
@@ -449,19 +892,15 @@ pub fn overlay(under: &str, over: &str, x: i32, y: i32) -> String {
     };
 
     // pad out end of each line
-    let line_width_deficit = under_width as i32 - over_width as i32;
-    let split_over: Vec<String> = if line_width_deficit.is_positive() {
-        split_over
-            .iter()
-            .map(|l| {
-                l.chars()
-                    .chain((0..line_width_deficit).map(|_| ' '))
-                    .collect()
-            })
-            .collect()
-    } else {
-        split_over
-    };
+    let split_over: Vec<String> = split_over
+        .iter()
+        .map(|l| {
+            let line_width_deficit = under_width as i32 - l.chars().count() as i32;
+            l.chars()
+                .chain((0..line_width_deficit.max(0)).map(|_| ' '))
+                .collect()
+        })
+        .collect();
 
     // Now that the dimensions match, overlay them
     let mut out: Vec<String> = vec![];
@@ -476,6 +915,11 @@ pub fn overlay(under: &str, over: &str, x: i32, y: i32) -> String {
     out.join("\n")
 }
 
+/// A line holding `text` centred within `width` characters
+pub(crate) fn centred_line(text: &str, width: usize) -> String {
+    format!("{: ^width$}", text, width = width)
+}
+
 pub fn empty_face(width: u32, height: u32) -> String {
     (0..height)
         .map(|_| " ".repeat(width as usize))
@@ -486,6 +930,7 @@ pub fn empty_face(width: u32, height: u32) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render;
 
     #[test]
     fn test_bins_for_cells() {
@@ -634,7 +1079,7 @@ mod tests {
     }
 
     #[test]
-    fn test_render_face_points() {
+    fn test_text_surface_points() {
         use crate::style::PointStyle;
         let data = vec![
             (-3.0, 2.3),
@@ -647,8 +1092,9 @@ mod tests {
         let x_axis = axis::ContinuousAxis::new(-3.575, 9.075, 6);
         let y_axis = axis::ContinuousAxis::new(-1.735, 5.635, 6);
         let style = PointStyle::new();
-        //TODO NEXT
-        let strings = render_face_points(&data, &x_axis, &y_axis, 20, 10, &style);
+        let mut surface = TextSurface::new(20, 10);
+        render::draw_face_points(&data, &x_axis, &y_axis, &mut surface, &style);
+        let strings = surface.to_string();
         assert_eq!(strings.lines().count(), 10);
         assert!(strings.lines().all(|s| s.chars().count() == 20));
 
@@ -669,6 +1115,21 @@ mod tests {
         assert_eq!(&strings, &comp);
     }
 
+    #[test]
+    fn test_text_surface_shapes() {
+        let mut surface = TextSurface::new(8, 4);
+        surface.rect((2., 1.), (3., 2.), &style::BoxStyle::new());
+        surface.line((0., 4.), (8., 4.), &style::LineStyle::new());
+        surface.text((8., 1.), "ab", TextAnchor::End, 12.);
+        surface.push_clip((8., 0.), (0., 4.));
+        surface.text((8., 2.), "cd", TextAnchor::End, 12.);
+        surface.pop_clip();
+
+        let comp = ["--------", " ----   ", " |  |  d", " ---- ab"].join("\n");
+
+        assert_eq!(surface.to_string(), comp);
+    }
+
     #[test]
     fn test_overlay() {
         let a = " ooo ";
@@ -700,6 +1161,11 @@ mod tests {
         let b = "    \n  # \n #  \n    ";
         let r = "o#\n#o";
         assert_eq!(overlay(a, b, -1, -1), r);
+
+        let a = "     \n     ";
+        let b = "●\n ●";
+        let r = " ●   \n  ●  ";
+        assert_eq!(overlay(a, b, 1, 0), r);
     }
 
     #[test]
@@ -710,4 +1176,14 @@ mod tests {
         assert_eq!(empty_face(2, 3), "  \n  \n  ");
         assert_eq!(empty_face(4, 2), "    \n    ");
     }
+
+    #[test]
+    fn test_render_grid() {
+        let x_axis = axis::ContinuousAxis::new(0., 4., 5);
+        let y_axis = axis::ContinuousAxis::new(0., 2., 3).minor_ticks(2);
+        let grid = Grid::at_ticks().include_minor(true);
+        let rendered = render_grid(&grid, Some(&x_axis), &y_axis, 8, 4);
+        let rows: Vec<&str> = rendered.split('\n').collect();
+        assert_eq!(rows, ["─┼─┼─┼─┼", "┈┼┈┼┈┼┈┼", "─┼─┼─┼─┼", "┈┼┈┼┈┼┈┼"]);
+    }
 }
@@ -0,0 +1,460 @@
+/*!
+
+Calendar calculations for time axes.
+
+Times are given as seconds since the Unix epoch and are always treated as UTC.
+
+*/
+
+use std::fmt::Write;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+
+/// The furthest a time can be from the epoch, in seconds, and still be put on the calendar.
+/// This is about thirty million years, far enough from the limits of the integer calculations.
+pub(crate) const MAX_TIMESTAMP: f64 = 1e15;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// A moment broken down into its calendar fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DateTime {
+    pub year: i64,
+    /// From 1 to 12
+    pub month: u32,
+    /// From 1 to 31
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    /// Fractions of a second
+    pub nanosecond: u32,
+    /// Days since Sunday, from 0 to 6
+    pub weekday: u32,
+    /// Days since the 1st of January, from 0 to 365
+    pub ordinal: u32,
+}
+
+/// The number of days from 1970-01-01 to the given date in the proleptic Gregorian calendar
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The year, month and day of the date a number of days after 1970-01-01
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+impl DateTime {
+    pub(crate) fn from_timestamp(timestamp: f64) -> DateTime {
+        let whole_seconds = timestamp.floor();
+        let nanosecond = (((timestamp - whole_seconds) * 1e9).round() as u32).min(999_999_999);
+        let whole_seconds = whole_seconds as i64;
+
+        let days = whole_seconds.div_euclid(SECONDS_PER_DAY);
+        let seconds_of_day = whole_seconds.rem_euclid(SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (seconds_of_day / SECONDS_PER_HOUR) as u32,
+            minute: (seconds_of_day % SECONDS_PER_HOUR / SECONDS_PER_MINUTE) as u32,
+            second: (seconds_of_day % SECONDS_PER_MINUTE) as u32,
+            nanosecond,
+            weekday: (days + 4).rem_euclid(7) as u32,
+            ordinal: (days - days_from_civil(year, 1, 1)) as u32,
+        }
+    }
+
+    /**
+    Format the time using a strftime-style pattern.
+
+    The supported specifiers are
+    `%Y`, `%y`, `%m`, `%d`, `%e`, `%j`, `%H`, `%I`, `%p`, `%M`, `%S`, `%f`,
+    `%b`, `%B`, `%a`, `%A` and `%%`.
+    Anything else is copied into the output unchanged.
+    */
+    pub(crate) fn format(&self, pattern: &str) -> String {
+        let mut out = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            // Writing to a String cannot fail
+            let _ = match chars.next() {
+                Some('Y') => write!(out, "{}", self.year),
+                Some('y') => write!(out, "{:02}", self.year.rem_euclid(100)),
+                Some('m') => write!(out, "{:02}", self.month),
+                Some('d') => write!(out, "{:02}", self.day),
+                Some('e') => write!(out, "{:>2}", self.day),
+                Some('j') => write!(out, "{:03}", self.ordinal + 1),
+                Some('H') => write!(out, "{:02}", self.hour),
+                Some('I') => write!(out, "{:02}", (self.hour + 11) % 12 + 1),
+                Some('p') => write!(out, "{}", if self.hour < 12 { "AM" } else { "PM" }),
+                Some('M') => write!(out, "{:02}", self.minute),
+                Some('S') => write!(out, "{:02}", self.second),
+                Some('f') => write!(out, "{:03}", self.nanosecond / 1_000_000),
+                Some('b') => write!(out, "{}", &MONTH_NAMES[self.month as usize - 1][..3]),
+                Some('B') => write!(out, "{}", MONTH_NAMES[self.month as usize - 1]),
+                Some('a') => write!(out, "{}", &DAY_NAMES[self.weekday as usize][..3]),
+                Some('A') => write!(out, "{}", DAY_NAMES[self.weekday as usize]),
+                Some('%') => write!(out, "%"),
+                Some(other) => write!(out, "%{}", other),
+                None => write!(out, "%"),
+            };
+        }
+        out
+    }
+}
+
+/// A calendar unit which time axis ticks can be spaced by
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub(crate) enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    /// The coarsest unit that a time lies exactly on a boundary of
+    pub(crate) fn of_timestamp(timestamp: f64) -> TimeUnit {
+        let t = DateTime::from_timestamp(timestamp);
+        if t.nanosecond != 0 || t.second != 0 {
+            TimeUnit::Second
+        } else if t.minute != 0 {
+            TimeUnit::Minute
+        } else if t.hour != 0 {
+            TimeUnit::Hour
+        } else if t.day != 1 {
+            TimeUnit::Day
+        } else if t.month != 1 {
+            TimeUnit::Month
+        } else {
+            TimeUnit::Year
+        }
+    }
+}
+
+/// A pattern which shows just enough of each time to tell them apart
+pub(crate) fn default_format(ticks: &[f64]) -> &'static str {
+    let finest_unit = ticks
+        .iter()
+        .map(|&tick| TimeUnit::of_timestamp(tick))
+        .fold(TimeUnit::Year, |a, b| if b < a { b } else { a });
+    let first_day = ticks.first().map(|&t| (t / SECONDS_PER_DAY as f64).floor());
+    let single_day = ticks
+        .iter()
+        .all(|&t| Some((t / SECONDS_PER_DAY as f64).floor()) == first_day);
+
+    match finest_unit {
+        TimeUnit::Second if single_day => "%H:%M:%S",
+        TimeUnit::Second => "%Y-%m-%d %H:%M:%S",
+        TimeUnit::Minute | TimeUnit::Hour if single_day => "%H:%M",
+        TimeUnit::Minute | TimeUnit::Hour => "%Y-%m-%d %H:%M",
+        TimeUnit::Day => "%Y-%m-%d",
+        TimeUnit::Month => "%Y-%m",
+        TimeUnit::Year => "%Y",
+    }
+}
+
+/// The intervals that ticks can be spaced by, from finest to coarsest
+const TICK_INTERVALS: [(TimeUnit, i64); 23] = [
+    (TimeUnit::Second, 1),
+    (TimeUnit::Second, 2),
+    (TimeUnit::Second, 5),
+    (TimeUnit::Second, 10),
+    (TimeUnit::Second, 15),
+    (TimeUnit::Second, 30),
+    (TimeUnit::Minute, 1),
+    (TimeUnit::Minute, 2),
+    (TimeUnit::Minute, 5),
+    (TimeUnit::Minute, 10),
+    (TimeUnit::Minute, 15),
+    (TimeUnit::Minute, 30),
+    (TimeUnit::Hour, 1),
+    (TimeUnit::Hour, 3),
+    (TimeUnit::Hour, 6),
+    (TimeUnit::Hour, 12),
+    (TimeUnit::Day, 1),
+    (TimeUnit::Day, 2),
+    (TimeUnit::Day, 7),
+    (TimeUnit::Day, 14),
+    (TimeUnit::Month, 1),
+    (TimeUnit::Month, 3),
+    (TimeUnit::Month, 6),
+];
+
+/// All the ticks between `lower` and `upper` which lie on a multiple of `step` of `unit`
+fn ticks_for_interval(lower: f64, upper: f64, unit: TimeUnit, step: i64) -> Vec<f64> {
+    let seconds = match unit {
+        TimeUnit::Second => Some(step),
+        TimeUnit::Minute => Some(step * SECONDS_PER_MINUTE),
+        TimeUnit::Hour => Some(step * SECONDS_PER_HOUR),
+        TimeUnit::Day => Some(step * SECONDS_PER_DAY),
+        TimeUnit::Month | TimeUnit::Year => None,
+    };
+
+    if let Some(seconds) = seconds {
+        // The epoch is at midnight, so multiples of these intervals all line up with the day.
+        // Whole weeks are shifted to start on a Monday, as the epoch was a Thursday.
+        let offset = if unit == TimeUnit::Day && step % 7 == 0 {
+            4 * SECONDS_PER_DAY
+        } else {
+            0
+        };
+        let first = ((lower - offset as f64) / seconds as f64).ceil() as i64;
+        let last = ((upper - offset as f64) / seconds as f64).floor() as i64;
+        return (first..=last)
+            .map(|n| (n * seconds + offset) as f64)
+            .collect();
+    }
+
+    // Count in months since year 0 so that years and months can be handled together
+    let step_months = if unit == TimeUnit::Year {
+        step * 12
+    } else {
+        step
+    };
+    let start = DateTime::from_timestamp(lower);
+    let mut months = start.year * 12 + i64::from(start.month) - 1;
+    months += (step_months - months.rem_euclid(step_months)) % step_months;
+
+    let mut ticks = vec![];
+    loop {
+        let days = days_from_civil(months.div_euclid(12), months.rem_euclid(12) as u32 + 1, 1);
+        let tick = (days * SECONDS_PER_DAY) as f64;
+        if tick > upper {
+            break;
+        }
+        if tick >= lower {
+            ticks.push(tick);
+        }
+        months += step_months;
+    }
+    ticks
+}
+
+/// A range around a single time, from the start of its day to the start of the next.
+/// A time at the very start of a day is given a day either side, so that it is not at the edge.
+pub(crate) fn day_around(timestamp: f64) -> (f64, f64) {
+    let day = SECONDS_PER_DAY as f64;
+    let start = (timestamp / day).floor() * day;
+    if start == timestamp {
+        (start - day, start + day)
+    } else {
+        (start, start + day)
+    }
+}
+
+/// Choose ticks on calendar boundaries between `lower` and `upper`, using no more than `max_ticks`
+pub(crate) fn calculate_time_ticks(lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+    let max_ticks = max_ticks.max(1) as f64;
+    let span = upper - lower;
+
+    for &(unit, step) in TICK_INTERVALS.iter() {
+        let approx_seconds = match unit {
+            TimeUnit::Second => 1.,
+            TimeUnit::Minute => 60.,
+            TimeUnit::Hour => 3600.,
+            TimeUnit::Day => 86400.,
+            TimeUnit::Month => 30. * 86400.,
+            TimeUnit::Year => 365. * 86400.,
+        } * step as f64;
+        // Skip any interval which would obviously give too many ticks before generating them
+        if span / approx_seconds > max_ticks + 1. {
+            continue;
+        }
+        let ticks = ticks_for_interval(lower, upper, unit, step);
+        if ticks.len() as f64 <= max_ticks {
+            return ticks;
+        }
+    }
+
+    // Beyond this the calendar no longer applies, and the steps in years could overflow
+    if span.is_nan() || span > 2. * MAX_TIMESTAMP {
+        return vec![];
+    }
+
+    // Fall back to years, in steps of 1, 2 or 5 times a power of ten,
+    // starting from the power of ten which gives about the right number of ticks
+    let years = span / (365.25 * 86400.);
+    let mut magnitude = 10f64.powf((years / max_ticks).log10().floor()).max(1.) as i64;
+    loop {
+        for &factor in &[1, 2, 5] {
+            let step = factor * magnitude;
+            if years / step as f64 <= max_ticks + 1. {
+                let ticks = ticks_for_interval(lower, upper, TimeUnit::Year, step);
+                if ticks.len() as f64 <= max_ticks {
+                    return ticks;
+                }
+            }
+        }
+        magnitude *= 10;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_around() {
+        // 2020-03-14 12:30 is within the day starting at 2020-03-14 00:00
+        assert_eq!(day_around(1_584_189_000.), (1_584_144_000., 1_584_230_400.));
+        assert_eq!(day_around(1_584_144_000.), (1_584_057_600., 1_584_230_400.));
+    }
+
+    #[test]
+    fn test_civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        for &days in &[-800_000, -1, 0, 1, 59, 11016, 11017, 19_723, 2_000_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_format() {
+        let t = DateTime::from_timestamp(1_700_000_000.25);
+        assert_eq!(t.format("%Y-%m-%d %H:%M:%S.%f"), "2023-11-14 22:13:20.250");
+        assert_eq!(t.format("%a %e %b %y, %I%p"), "Tue 14 Nov 23, 10PM");
+        assert_eq!(
+            t.format("%A %B day %j 100%%"),
+            "Tuesday November day 318 100%"
+        );
+
+        let t = DateTime::from_timestamp(-1.);
+        assert_eq!(t.format("%Y-%m-%d %H:%M:%S"), "1969-12-31 23:59:59");
+    }
+
+    #[test]
+    fn test_time_unit_of_timestamp() {
+        let day = SECONDS_PER_DAY as f64;
+        assert_eq!(TimeUnit::of_timestamp(0.), TimeUnit::Year);
+        assert_eq!(TimeUnit::of_timestamp(31. * day), TimeUnit::Month);
+        assert_eq!(TimeUnit::of_timestamp(2. * day), TimeUnit::Day);
+        assert_eq!(TimeUnit::of_timestamp(3600.), TimeUnit::Hour);
+        assert_eq!(TimeUnit::of_timestamp(60.), TimeUnit::Minute);
+        assert_eq!(TimeUnit::of_timestamp(0.5), TimeUnit::Second);
+    }
+
+    #[test]
+    fn test_default_format() {
+        let day = SECONDS_PER_DAY as f64;
+        assert_eq!(default_format(&[0., 365. * day]), "%Y");
+        assert_eq!(default_format(&[0., 31. * day]), "%Y-%m");
+        assert_eq!(default_format(&[day, 2. * day]), "%Y-%m-%d");
+        assert_eq!(default_format(&[day, day + 3600.]), "%H:%M");
+        assert_eq!(default_format(&[1.5 * day, 2.5 * day]), "%Y-%m-%d %H:%M");
+        assert_eq!(default_format(&[day, day + 1.]), "%H:%M:%S");
+    }
+
+    #[test]
+    fn test_calculate_time_ticks() {
+        let format = |ticks: Vec<f64>, pattern: &str| -> Vec<String> {
+            ticks
+                .iter()
+                .map(|&t| DateTime::from_timestamp(t).format(pattern))
+                .collect()
+        };
+
+        // One minute
+        let ticks = calculate_time_ticks(1_700_000_000., 1_700_000_060., 6);
+        assert_eq!(format(ticks, "%M:%S"), ["13:30", "13:45", "14:00", "14:15"]);
+
+        // A few days
+        let start = (days_from_civil(2024, 2, 27) * SECONDS_PER_DAY) as f64 + 3600.;
+        let ticks = calculate_time_ticks(start, start + 4. * 86400., 6);
+        assert_eq!(format(ticks, "%m-%d"), ["02-28", "02-29", "03-01", "03-02"]);
+
+        // A couple of years
+        let start = (days_from_civil(2022, 11, 5) * SECONDS_PER_DAY) as f64;
+        let end = (days_from_civil(2024, 8, 1) * SECONDS_PER_DAY) as f64;
+        let ticks = calculate_time_ticks(start, end, 8);
+        assert_eq!(
+            format(ticks, "%Y-%m"),
+            ["2023-01", "2023-04", "2023-07", "2023-10", "2024-01", "2024-04", "2024-07"]
+        );
+
+        // A few weeks, starting on Mondays
+        let start = (days_from_civil(2024, 3, 1) * SECONDS_PER_DAY) as f64;
+        let ticks = calculate_time_ticks(start, start + 30. * 86400., 6);
+        assert_eq!(
+            format(ticks, "%a %d"),
+            ["Mon 04", "Mon 11", "Mon 18", "Mon 25"]
+        );
+
+        // Decades
+        let start = (days_from_civil(1931, 1, 1) * SECONDS_PER_DAY) as f64;
+        let end = (days_from_civil(2024, 1, 1) * SECONDS_PER_DAY) as f64;
+        let ticks = calculate_time_ticks(start, end, 6);
+        assert_eq!(
+            format(ticks, "%Y"),
+            ["1940", "1960", "1980", "2000", "2020"]
+        );
+
+        // Millions of years, without stepping up from a single year
+        let ticks = calculate_time_ticks(0., 1e14, 6);
+        assert!(!ticks.is_empty() && ticks.len() <= 6);
+        assert_eq!(format(ticks, "%Y")[0], "500000");
+
+        // Too far for the calendar
+        assert!(calculate_time_ticks(0., 1e300, 6).is_empty());
+    }
+}
@@ -12,7 +12,7 @@ impl<T> PairWise<T> for [T] {
     }
 }
 
-fn _mean(s: &[f64]) -> f64 {
+pub fn mean(s: &[f64]) -> f64 {
     s.iter().map(|v| v / s.len() as f64).sum()
 }
 
@@ -39,6 +39,47 @@ pub fn quartiles(s: &[f64]) -> (f64, f64, f64) {
     (median(a), median(&s), median(b))
 }
 
+/// The value below which `p` percent of the numbers lie,
+/// interpolating linearly between the nearest two
+pub fn percentile(s: &[f64], p: f64) -> f64 {
+    let mut s = s.to_owned();
+    s.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let position = (p / 100.).clamp(0., 1.) * (s.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    s[below] + (position - below as f64) * (s[above] - s[below])
+}
+
+/// The standard deviation of a sample of numbers
+pub fn std_dev(s: &[f64]) -> f64 {
+    let m = mean(s);
+    let variance = s.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (s.len() as f64 - 1.);
+    variance.sqrt()
+}
+
+/// A bandwidth for a kernel density estimate of the numbers, by Silverman's rule of thumb
+///
+/// If the numbers are all the same, this falls back to a bandwidth of 1.
+pub fn silverman_bandwidth(s: &[f64]) -> f64 {
+    let (q1, _, q3) = quartiles(s);
+    let spread = if s.len() > 1 { std_dev(s) } else { 0. };
+    let spread = if q3 > q1 { spread.min((q3 - q1) / 1.34) } else { spread };
+    let bandwidth = 0.9 * spread * (s.len() as f64).powf(-0.2);
+    if bandwidth > 0. {
+        bandwidth
+    } else {
+        1.
+    }
+}
+
+/// The density at `x` estimated from the numbers with a Gaussian kernel of the given bandwidth
+pub fn gaussian_kde(s: &[f64], bandwidth: f64, x: f64) -> f64 {
+    let norm = 1. / ((2. * f64::consts::PI).sqrt() * bandwidth * s.len() as f64);
+    s.iter()
+        .map(|v| (-0.5 * ((x - v) / bandwidth).powi(2)).exp())
+        .sum::<f64>()
+        * norm
+}
+
 /// Given a slice of numbers, return the minimum and maximum values
 pub fn range(s: &[f64]) -> (f64, f64) {
     let mut min = f64::INFINITY;
@@ -62,6 +103,95 @@ pub fn pad_range_to_zero(min: f64, max: f64) -> (f64, f64) {
     }
 }
 
+/// A small pseudo-random number generator (SplitMix64), so that output is the same for the same seed
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Whether a point lies inside the polygon with the given corners, by the even-odd rule
+pub fn point_in_polygon((x, y): (f64, f64), corners: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    let mut previous = match corners.last() {
+        Some(&corner) => corner,
+        None => return false,
+    };
+    for &corner in corners {
+        let ((x1, y1), (x2, y2)) = (previous, corner);
+        if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+        previous = corner;
+    }
+    inside
+}
+
+/// The part of the line segment from `start` to `end` which lies within the rectangle
+/// from `min` to `max`, by the Liang-Barsky algorithm
+///
+/// Returns `None` if none of the segment lies within the rectangle,
+/// or if either end of it is not a finite point.
+pub fn clip_segment(
+    start: (f64, f64),
+    end: (f64, f64),
+    min: (f64, f64),
+    max: (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let ends = [start.0, start.1, end.0, end.1];
+    if !ends.iter().all(|v| v.is_finite()) {
+        return None;
+    }
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let (mut t0, mut t1) = (0f64, 1f64);
+    // Each edge of the rectangle as the rate the segment moves towards its outside,
+    // and the distance of the start of the segment inside it
+    let edges = [
+        (-dx, start.0 - min.0),
+        (dx, max.0 - start.0),
+        (-dy, start.1 - min.1),
+        (dy, max.1 - start.1),
+    ];
+    for &(p, q) in &edges {
+        if p == 0. {
+            if q < 0. {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0. {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    Some((
+        (start.0 + t0 * dx, start.1 + t0 * dy),
+        (start.0 + t1 * dx, start.1 + t1 * dy),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,9 +218,9 @@ mod tests {
     #[test]
     fn test_mean() {
         // TODO should error: mean(&[]);
-        assert_eq!(_mean(&[1.]), 1.);
-        assert_eq!(_mean(&[1., 2.]), 1.5);
-        assert_eq!(_mean(&[1., 2., 3.]), 2.);
+        assert_eq!(mean(&[1.]), 1.);
+        assert_eq!(mean(&[1., 2.]), 1.5);
+        assert_eq!(mean(&[1., 2., 3.]), 2.);
     }
 
     #[test]
@@ -111,10 +241,94 @@ mod tests {
         assert_eq!(quartiles(&[1., 2., 3., 4.]), (1.5, 2.5, 3.5));
     }
 
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[3.], 50.), 3.);
+        assert_eq!(percentile(&[4., 1., 3., 2., 5.], 0.), 1.);
+        assert_eq!(percentile(&[4., 1., 3., 2., 5.], 50.), 3.);
+        assert_eq!(percentile(&[4., 1., 3., 2., 5.], 100.), 5.);
+        assert_eq!(percentile(&[1., 2., 3., 4., 5.], 90.), 4.6);
+    }
+
+    #[test]
+    fn test_std_dev() {
+        assert_eq!(std_dev(&[1., 1., 1.]), 0.);
+        assert_eq!(std_dev(&[2., 4., 4., 4., 5., 5., 7., 9.]), (32f64 / 7.).sqrt());
+    }
+
+    #[test]
+    fn test_silverman_bandwidth() {
+        assert_eq!(silverman_bandwidth(&[3., 3., 3.]), 1.);
+        let bandwidth = silverman_bandwidth(&[1., 2., 3., 4., 5.]);
+        // The standard deviation is smaller than the interquartile range over 1.34
+        let expected = 0.9 * 2.5f64.sqrt() * 5f64.powf(-0.2);
+        assert!((bandwidth - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gaussian_kde() {
+        let peak = 1. / (2. * f64::consts::PI).sqrt();
+        assert!((gaussian_kde(&[0.], 1., 0.) - peak).abs() < 1e-12);
+        assert!((gaussian_kde(&[-1., 1.], 1., 1.) - gaussian_kde(&[-1., 1.], 1., -1.)).abs() < 1e-12);
+        // The density integrates to one
+        let area: f64 = (-600..600)
+            .map(|i| gaussian_kde(&[0., 1., 5.], 0.5, f64::from(i) / 50.) / 50.)
+            .sum();
+        assert!((area - 1.).abs() < 1e-6);
+    }
+
     #[test]
     fn test_pad_range_to_zero() {
         assert_eq!(pad_range_to_zero(2.0, 2.0), (0.0, 2.0));
         assert_eq!(pad_range_to_zero(-2.0, 2.0), (-2.0, 2.0));
         assert_eq!(pad_range_to_zero(-2.0, -2.0), (-2.0, 0.0));
     }
+
+    #[test]
+    fn test_rng() {
+        let first: Vec<u64> = {
+            let mut rng = Rng::new(42);
+            (0..4).map(|_| rng.next_u64()).collect()
+        };
+        let mut rng = Rng::new(42);
+        assert_eq!((0..4).map(|_| rng.next_u64()).collect::<Vec<_>>(), first);
+        assert_ne!(Rng::new(43).next_u64(), first[0]);
+
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let v = rng.next_f64();
+            assert!((0. ..1.).contains(&v));
+        }
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        // An L shape, which is not convex
+        let l = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)];
+        assert!(point_in_polygon((0.5, 0.5), &l));
+        assert!(point_in_polygon((0.5, 1.5), &l));
+        assert!(!point_in_polygon((1.5, 1.5), &l));
+        assert!(!point_in_polygon((3., 0.5), &l));
+        assert!(!point_in_polygon((0.5, 0.5), &[]));
+    }
+
+    #[test]
+    fn test_clip_segment() {
+        let (min, max) = ((0., 0.), (10., 5.));
+        assert_eq!(
+            clip_segment((1., 1.), (2., 2.), min, max),
+            Some(((1., 1.), (2., 2.)))
+        );
+        assert_eq!(
+            clip_segment((-10., 1.), (1e9, 1.), min, max),
+            Some(((0., 1.), (10., 1.)))
+        );
+        assert_eq!(
+            clip_segment((5., -5.), (5., 10.), min, max),
+            Some(((5., 0.), (5., 5.)))
+        );
+        assert_eq!(clip_segment((11., 1.), (20., 2.), min, max), None);
+        assert_eq!(clip_segment((-1., 6.), (1., 8.), min, max), None);
+        assert_eq!(clip_segment((f64::NAN, 1.), (2., 2.), min, max), None);
+    }
 }
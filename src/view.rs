@@ -9,34 +9,268 @@ extent to plot and information about the axes. It knows how to render itself.
 use crate::errors;
 use std;
 use std::f64;
-
-use svg::Node;
+use std::rc::Rc;
 
 use crate::axis;
 use crate::errors::Result;
-use crate::grid::{Grid, GridType};
+use crate::grid::Grid;
+use crate::legend::{Legend, LegendPosition};
+use crate::page::Margins;
+use crate::render;
+use crate::render::{AxisSide, Surface};
 use crate::repr::{CategoricalRepresentation, ContinuousRepresentation};
-use crate::svg_render;
-use crate::text_render;
-use crate::utils;
+use crate::svg_render::SvgSurface;
+use crate::text_render::TextSurface;
 
 pub trait View {
-    fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group>;
-    fn to_text(&self, face_width: u32, face_height: u32) -> Result<String>;
+    /// Draw the view onto a surface the size of its face,
+    /// with the axes, legend and title laid out around the face.
+    fn draw(&self, surface: &mut dyn Surface) -> Result<()>;
+
+    /**
+    Create an SVG rendering of the view
+
+    The ids of any clip regions in it are unique, so that several renderings can go in one document.
+    */
+    fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group> {
+        let mut surface = SvgSurface::new(face_width, face_height);
+        self.draw(&mut surface)?;
+        Ok(surface.into_group())
+    }
+
+    /**
+    Create a text rendering of the view
+    */
+    fn to_text(&self, face_width: u32, face_height: u32) -> Result<String> {
+        let mut surface = TextSurface::new(face_width, face_height);
+        self.draw(&mut surface)?;
+        Ok(surface.to_string())
+    }
+
     fn add_grid(&mut self, grid: Grid);
     fn grid(&self) -> &Option<Grid>;
+
+    /// The title shown above the view, if it has one.
+    ///
+    /// Space is made for it above the face when the view is laid out on a page.
+    fn get_title(&self) -> Option<&str> {
+        None
+    }
+
+    /// The margins needed around the face to fit the axes, labels and title
+    /// when the whole view is `width` wide.
+    fn margins(&self, _width: f64) -> Result<Margins> {
+        let top = if self.get_title().is_some() { 48. } else { 24. };
+        Ok(Margins::new(72., 48., top, 36.))
+    }
+}
+
+/// The space between the axes and a legend placed outside of the face
+const LEGEND_GAP: f64 = 10.;
+
+/// The space taken by a legend placed outside of the face, to the right of it and below it
+fn outside_legend_space(legend: &Legend, entries: &[render::LegendEntry]) -> (f64, f64) {
+    if entries.is_empty() {
+        return (0., 0.);
+    }
+    let (width, height) = render::legend_size(legend, entries);
+    // Leave a gap either side, so the legend does not run up to the edge of the view
+    match legend.get_position() {
+        LegendPosition::OutsideRight => (width + 2. * LEGEND_GAP, 0.),
+        LegendPosition::OutsideBottom => (0., height + 2. * LEGEND_GAP),
+        _ => (0., 0.),
+    }
+}
+
+/**
+Where the top-left corner of the legend goes, relative to the bottom-left corner of the face
+
+`right_width` is the space taken by the axis to the right of the face and `x_label` is the label below it.
+To find the best corner, `draw` is called to see where the representations are drawn.
+*/
+fn legend_origin<F>(
+    legend: &Legend,
+    entries: &[render::LegendEntry],
+    right_width: f64,
+    x_label: &str,
+    face_size: (f64, f64),
+    draw: F,
+) -> (f64, f64)
+where
+    F: FnOnce(&mut dyn render::Surface),
+{
+    let size = render::legend_size(legend, entries);
+    let (face_width, face_height) = face_size;
+    match legend.get_position() {
+        LegendPosition::OutsideRight => (face_width + right_width + LEGEND_GAP, face_height),
+        LegendPosition::OutsideBottom => (
+            (face_width - size.0) / 2.,
+            -(render::horizontal_axis_height(x_label) + LEGEND_GAP),
+        ),
+        LegendPosition::Best => {
+            // See where everything is drawn and pick the corner which hides the least of it
+            let mut footprint = render::FootprintSurface::new(face_width, face_height);
+            draw(&mut footprint);
+            let corner = render::best_legend_corner(size, face_size, &footprint.points);
+            render::legend_corner(corner, size, face_size)
+        }
+        corner => render::legend_corner(corner, size, face_size),
+    }
+}
+
+/// The space above the face of a view, with or without a title
+fn top_margin(title: &Option<String>) -> f64 {
+    if title.is_some() {
+        32.
+    } else {
+        14.
+    }
+}
+
+/// The space between the right edge of the face and anything placed beyond it,
+/// with a continuous `x_axis` along the bottom and an optional `y2_axis` up the right
+fn right_axis_width(
+    x_axis: &axis::ContinuousAxis,
+    y2_axis: Option<&axis::ContinuousAxis>,
+    face_width: f64,
+) -> f64 {
+    let axis_width = y2_axis.map_or(10., render::vertical_axis_width);
+    let (_, overhang) = render::horizontal_axis_overhang(x_axis, face_width);
+    axis_width.max(overhang + 4.)
+}
+
+/// The settings for one of the continuous axes of a `ContinuousView`
+struct AxisOptions {
+    range: Option<axis::Range>,
+    transform: Rc<dyn axis::AxisTransform>,
+    tick_format: Option<axis::TickFormat>,
+    ticks: Option<Vec<f64>>,
+    tick_labels: Option<Vec<String>>,
+    minor_ticks: usize,
+    max_ticks: usize,
+    label: Option<String>,
+}
+
+impl AxisOptions {
+    fn new() -> Self {
+        AxisOptions {
+            range: None,
+            transform: Rc::new(axis::Linear),
+            tick_format: None,
+            ticks: None,
+            tick_labels: None,
+            minor_ticks: 0,
+            max_ticks: 6,
+            label: None,
+        }
+    }
+
+    fn labelled_ticks<S: AsRef<str>>(&mut self, ticks: &[(f64, S)]) {
+        self.ticks = Some(ticks.iter().map(|(t, _)| *t).collect());
+        self.tick_labels = Some(ticks.iter().map(|(_, l)| l.as_ref().into()).collect());
+    }
+
+    /// The range covering dimension `dim` of all the representations
+    fn default_range(&self, reprs: &[&dyn ContinuousRepresentation], dim: u32) -> axis::Range {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for repr in reprs {
+            // If bars can't be drawn from zero then it doesn't need to be on the axis
+            let (this_min, this_max) = if self.transform.is_valid_value(0.) {
+                repr.range(dim)
+            } else {
+                repr.data_range(dim)
+            };
+            min = min.min(this_min);
+            max = max.max(this_max);
+        }
+        let (min, max) = self.transform.pad_range(min, max);
+        axis::Range::new(min, max)
+    }
+
+    /// Check that all the data, and the range given for an axis, can be shown with its transform
+    fn check_scale(
+        &self,
+        name: &str,
+        reprs: &[&dyn ContinuousRepresentation],
+        dim: u32,
+        range: &axis::Range,
+    ) -> Result<()> {
+        self.transform
+            .validate()
+            .map_err(|reason| errors::Error::InvalidAxisTransform {
+                name: String::from(name),
+                reason,
+            })?;
+        // Check both ends, as some transforms are bounded above as well as below
+        let data_bounds = reprs.iter().flat_map(|r| {
+            let (min, max) = r.data_range(dim);
+            vec![min, max]
+        });
+        for value in [range.lower, range.upper].into_iter().chain(data_bounds) {
+            if !self.transform.is_valid_value(value) && value.is_finite() {
+                return Err(errors::Error::InvalidAxisValue {
+                    name: String::from(name),
+                    value,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Create the axis showing dimension `dim` of the representations
+    fn create_axis(
+        &self,
+        name: &str,
+        reprs: &[&dyn ContinuousRepresentation],
+        dim: u32,
+    ) -> Result<axis::ContinuousAxis> {
+        let default_range = self.default_range(reprs, dim);
+        let range = self.range.as_ref().unwrap_or(&default_range);
+        self.check_scale(name, reprs, dim, range)?;
+        if !range.is_valid() {
+            return Err(errors::Error::InvalidRange {
+                name: String::from(name),
+                lower: range.lower,
+                upper: range.upper,
+            });
+        }
+
+        let label: String = self.label.clone().unwrap_or_else(|| "".to_string());
+        let mut axis = axis::ContinuousAxis::with_transform(
+            range.lower,
+            range.upper,
+            self.max_ticks,
+            self.transform.clone(),
+        )
+        .label(label);
+        if let Some(ref format) = self.tick_format {
+            axis = axis.tick_format(format.clone());
+        }
+        match (&self.ticks, &self.tick_labels) {
+            (Some(ticks), Some(labels)) => {
+                let labelled: Vec<_> = ticks.iter().cloned().zip(labels.iter()).collect();
+                axis = axis.labelled_ticks(&labelled);
+            }
+            (Some(ticks), None) => axis = axis.tick_positions(ticks),
+            _ => {}
+        }
+        Ok(axis.minor_ticks(self.minor_ticks))
+    }
 }
 
 /// Standard 1-dimensional view with a continuous x-axis
-#[derive(Default)]
+///
+/// Representations added with `add_y2` are drawn against a second y-axis
+/// up the right-hand side of the view, which has its own range, label and ticks.
 pub struct ContinuousView {
     representations: Vec<Box<dyn ContinuousRepresentation>>,
-    x_range: Option<axis::Range>,
-    y_range: Option<axis::Range>,
-    x_max_ticks: usize,
-    y_max_ticks: usize,
-    x_label: Option<String>,
-    y_label: Option<String>,
+    y2_representations: Vec<Box<dyn ContinuousRepresentation>>,
+    x: AxisOptions,
+    y: AxisOptions,
+    y2: AxisOptions,
+    title: Option<String>,
+    legend: Legend,
     grid: Option<Grid>,
 }
 
@@ -45,23 +279,28 @@ impl ContinuousView {
     pub fn new() -> ContinuousView {
         ContinuousView {
             representations: vec![],
-            x_range: None,
-            y_range: None,
-            x_max_ticks: 6,
-            y_max_ticks: 6,
-            x_label: None,
-            y_label: None,
+            y2_representations: vec![],
+            x: AxisOptions::new(),
+            y: AxisOptions::new(),
+            y2: AxisOptions::new(),
+            title: None,
+            legend: Legend::new(),
             grid: None,
         }
     }
     /// Set the maximum number of ticks along the x axis.
     pub fn x_max_ticks(mut self, val: usize) -> Self {
-        self.x_max_ticks = val;
+        self.x.max_ticks = val;
         self
     }
     /// Set the maximum number of ticks along the y axis.
     pub fn y_max_ticks(mut self, val: usize) -> Self {
-        self.y_max_ticks = val;
+        self.y.max_ticks = val;
+        self
+    }
+    /// Set the maximum number of ticks along the secondary y axis.
+    pub fn y2_max_ticks(mut self, val: usize) -> Self {
+        self.y2.max_ticks = val;
         self
     }
 
@@ -71,15 +310,166 @@ impl ContinuousView {
         self
     }
 
+    /// Add a representation to the view, plotted against the secondary y-axis on the right
+    pub fn add_y2<R: ContinuousRepresentation + 'static>(mut self, repr: R) -> Self {
+        self.y2_representations.push(Box::new(repr));
+        self
+    }
+
     /// Set the x range for the view
     pub fn x_range(mut self, min: f64, max: f64) -> Self {
-        self.x_range = Some(axis::Range::new(min, max));
+        self.x.range = Some(axis::Range::new(min, max));
         self
     }
 
     /// Set the y range for the view
     pub fn y_range(mut self, min: f64, max: f64) -> Self {
-        self.y_range = Some(axis::Range::new(min, max));
+        self.y.range = Some(axis::Range::new(min, max));
+        self
+    }
+
+    /// Set the range of the secondary y-axis.
+    ///
+    /// This shows the secondary y-axis even if no representations have been added to it.
+    pub fn y2_range(mut self, min: f64, max: f64) -> Self {
+        self.y2.range = Some(axis::Range::new(min, max));
+        self
+    }
+
+    /// Use a logarithmic scale with the given base for the x-axis.
+    /// The base must be positive and not 1.
+    ///
+    /// All data in the view must then be positive.
+    pub fn x_log(self, base: f64) -> Self {
+        self.x_transform(axis::Log::new(base))
+    }
+
+    /// Use a logarithmic scale with the given base for the y-axis.
+    /// The base must be positive and not 1.
+    ///
+    /// All data in the view must then be positive.
+    pub fn y_log(self, base: f64) -> Self {
+        self.y_transform(axis::Log::new(base))
+    }
+
+    /// Use a logarithmic scale with the given base for the secondary y-axis.
+    /// The base must be positive and not 1.
+    ///
+    /// All data plotted against it must then be positive.
+    pub fn y2_log(self, base: f64) -> Self {
+        self.y2_transform(axis::Log::new(base))
+    }
+
+    /// Treat the x values as times, given as seconds since the Unix epoch,
+    /// and place the ticks on calendar boundaries.
+    ///
+    /// Use `x_transform` with an `axis::Time` to set the format of the tick labels.
+    pub fn x_time(self) -> Self {
+        self.x_transform(axis::Time::new())
+    }
+
+    /// Set how values are spaced along the x-axis.
+    ///
+    /// All data in the view must be within the domain of the transform.
+    pub fn x_transform<T: axis::AxisTransform + 'static>(mut self, transform: T) -> Self {
+        self.x.transform = Rc::new(transform);
+        self
+    }
+
+    /// Set how values are spaced along the y-axis.
+    ///
+    /// All data in the view must be within the domain of the transform.
+    pub fn y_transform<T: axis::AxisTransform + 'static>(mut self, transform: T) -> Self {
+        self.y.transform = Rc::new(transform);
+        self
+    }
+
+    /// Set how values are spaced along the secondary y-axis.
+    ///
+    /// All data plotted against it must be within the domain of the transform.
+    pub fn y2_transform<T: axis::AxisTransform + 'static>(mut self, transform: T) -> Self {
+        self.y2.transform = Rc::new(transform);
+        self
+    }
+
+    /// Set how the ticks on the x-axis are labelled.
+    ///
+    /// By default the labels are chosen by the transform of the axis.
+    pub fn x_tick_format(mut self, format: axis::TickFormat) -> Self {
+        self.x.tick_format = Some(format);
+        self
+    }
+
+    /// Set how the ticks on the y-axis are labelled.
+    ///
+    /// By default the labels are chosen by the transform of the axis.
+    pub fn y_tick_format(mut self, format: axis::TickFormat) -> Self {
+        self.y.tick_format = Some(format);
+        self
+    }
+
+    /// Set how the ticks on the secondary y-axis are labelled.
+    pub fn y2_tick_format(mut self, format: axis::TickFormat) -> Self {
+        self.y2.tick_format = Some(format);
+        self
+    }
+
+    /// Put the ticks on the x-axis at exactly these values
+    pub fn x_ticks(mut self, ticks: &[f64]) -> Self {
+        self.x.ticks = Some(ticks.into());
+        self.x.tick_labels = None;
+        self
+    }
+
+    /// Put the ticks on the y-axis at exactly these values
+    pub fn y_ticks(mut self, ticks: &[f64]) -> Self {
+        self.y.ticks = Some(ticks.into());
+        self.y.tick_labels = None;
+        self
+    }
+
+    /// Put the ticks on the secondary y-axis at exactly these values
+    pub fn y2_ticks(mut self, ticks: &[f64]) -> Self {
+        self.y2.ticks = Some(ticks.into());
+        self.y2.tick_labels = None;
+        self
+    }
+
+    /// Put the ticks on the x-axis at exactly these values, each labelled with the accompanying text
+    pub fn x_labelled_ticks<S: AsRef<str>>(mut self, ticks: &[(f64, S)]) -> Self {
+        self.x.labelled_ticks(ticks);
+        self
+    }
+
+    /// Put the ticks on the y-axis at exactly these values, each labelled with the accompanying text
+    pub fn y_labelled_ticks<S: AsRef<str>>(mut self, ticks: &[(f64, S)]) -> Self {
+        self.y.labelled_ticks(ticks);
+        self
+    }
+
+    /// Put the ticks on the secondary y-axis at exactly these values,
+    /// each labelled with the accompanying text
+    pub fn y2_labelled_ticks<S: AsRef<str>>(mut self, ticks: &[(f64, S)]) -> Self {
+        self.y2.labelled_ticks(ticks);
+        self
+    }
+
+    /// Divide the space between the ticks on the x-axis into `subdivisions` parts with minor ticks
+    pub fn x_minor_ticks(mut self, subdivisions: usize) -> Self {
+        self.x.minor_ticks = subdivisions;
+        self
+    }
+
+    /// Divide the space between the ticks on the y-axis into `subdivisions` parts with minor ticks
+    pub fn y_minor_ticks(mut self, subdivisions: usize) -> Self {
+        self.y.minor_ticks = subdivisions;
+        self
+    }
+
+    /// Divide the space between the ticks on the secondary y-axis
+    /// into `subdivisions` parts with minor ticks
+    pub fn y2_minor_ticks(mut self, subdivisions: usize) -> Self {
+        self.y2.minor_ticks = subdivisions;
         self
     }
 
@@ -88,7 +478,7 @@ impl ContinuousView {
     where
         T: Into<String>,
     {
-        self.x_label = Some(value.into());
+        self.x.label = Some(value.into());
         self
     }
 
@@ -97,150 +487,150 @@ impl ContinuousView {
     where
         T: Into<String>,
     {
-        self.y_label = Some(value.into());
+        self.y.label = Some(value.into());
         self
     }
 
-    fn default_x_range(&self) -> axis::Range {
-        let mut x_min = f64::INFINITY;
-        let mut x_max = f64::NEG_INFINITY;
-        for repr in &self.representations {
-            let (this_x_min, this_x_max) = repr.range(0);
-            x_min = x_min.min(this_x_min);
-            x_max = x_max.max(this_x_max);
-        }
-        let (x_min, x_max) = utils::pad_range_to_zero(x_min, x_max);
-        axis::Range::new(x_min, x_max)
+    /// Set the label for the secondary y-axis
+    pub fn y2_label<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.y2.label = Some(value.into());
+        self
     }
 
-    fn default_y_range(&self) -> axis::Range {
-        let mut y_min = f64::INFINITY;
-        let mut y_max = f64::NEG_INFINITY;
-        for repr in &self.representations {
-            let (this_y_min, this_y_max) = repr.range(1);
-            y_min = y_min.min(this_y_min);
-            y_max = y_max.max(this_y_max);
-        }
-        let (y_min, y_max) = utils::pad_range_to_zero(y_min, y_max);
-        axis::Range::new(y_min, y_max)
+    /// Set the title shown above the view
+    pub fn title<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.title = Some(value.into());
+        self
     }
 
-    fn create_axes(&self) -> Result<(axis::ContinuousAxis, axis::ContinuousAxis)> {
-        let default_x_range = self.default_x_range();
-        let x_range = self.x_range.as_ref().unwrap_or(&default_x_range);
-        if !x_range.is_valid() {
-            return Err(errors::Error::InvalidRange {
-                name: String::from("x"),
-                lower: x_range.lower,
-                upper: x_range.upper,
-            });
-        }
+    /// Set where the legend is placed and how it looks
+    pub fn legend(mut self, legend: Legend) -> Self {
+        self.legend = legend;
+        self
+    }
 
-        let default_y_range = self.default_y_range();
-        let y_range = self.y_range.as_ref().unwrap_or(&default_y_range);
-        if !y_range.is_valid() {
-            return Err(errors::Error::InvalidRange {
-                name: String::from("y"),
-                lower: x_range.lower,
-                upper: x_range.upper,
-            });
-        }
+    /// Whether the secondary y-axis is shown
+    fn has_y2(&self) -> bool {
+        !self.y2_representations.is_empty() || self.y2.range.is_some()
+    }
 
-        let x_label: String = self.x_label.clone().unwrap_or_else(|| "".to_string());
-        let y_label: String = self.y_label.clone().unwrap_or_else(|| "".to_string());
+    /// Create the x-axis, the y-axis and, if it is shown, the secondary y-axis
+    fn create_axes(
+        &self,
+    ) -> Result<(
+        axis::ContinuousAxis,
+        axis::ContinuousAxis,
+        Option<axis::ContinuousAxis>,
+    )> {
+        let primary: Vec<_> = self.representations.iter().map(|r| r.as_ref()).collect();
+        let secondary: Vec<_> = self.y2_representations.iter().map(|r| r.as_ref()).collect();
+        let all: Vec<_> = primary.iter().chain(&secondary).cloned().collect();
+        for repr in &all {
+            repr.check()?;
+        }
 
-        let x_axis = axis::ContinuousAxis::new(x_range.lower, x_range.upper, self.x_max_ticks)
-            .label(x_label);
-        let y_axis = axis::ContinuousAxis::new(y_range.lower, y_range.upper, self.y_max_ticks)
-            .label(y_label);
+        let x_axis = self.x.create_axis("x", &all, 0)?;
+        let y_axis = self.y.create_axis("y", &primary, 1)?;
+        let y2_axis = if self.has_y2() {
+            Some(self.y2.create_axis("y2", &secondary, 1)?)
+        } else {
+            None
+        };
 
-        Ok((x_axis, y_axis))
+        Ok((x_axis, y_axis, y2_axis))
     }
-}
-
-impl View for ContinuousView {
-    /**
-    Create an SVG rendering of the view
-    */
-    fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group> {
-        let mut view_group = svg::node::element::Group::new();
-
-        let (x_axis, y_axis) = self.create_axes()?;
 
-        let (legend_x, mut legend_y) = (face_width - 100., -23.);
-        if let Some(grid) = &self.grid {
-            view_group.append(svg_render::draw_grid(
-                GridType::Both(grid),
-                face_width,
-                face_height,
-            ));
-        }
+    /// The legend entries of all the representations,
+    /// marked with which y-axis they belong to if there is a secondary one
+    fn legend_entries(&self) -> Vec<render::LegendEntry> {
+        let mark = |reprs: &[Box<dyn ContinuousRepresentation>], side: &str| {
+            reprs
+                .iter()
+                .filter_map(|repr| repr.legend())
+                .map(|mut entry| {
+                    if self.has_y2() {
+                        entry.label = format!("{} ({})", entry.label, side);
+                    }
+                    entry
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut entries = mark(&self.representations, "left");
+        entries.extend(mark(&self.y2_representations, "right"));
+        entries
+    }
 
-        // Then, based on those ranges, draw each repr as an SVG
+    /// Draw the representations on both y-axes onto a surface
+    fn draw_representations(
+        &self,
+        x_axis: &axis::ContinuousAxis,
+        y_axis: &axis::ContinuousAxis,
+        y2_axis: Option<&axis::ContinuousAxis>,
+        surface: &mut dyn render::Surface,
+    ) {
         for repr in &self.representations {
-            let repr_group = repr.to_svg(&x_axis, &y_axis, face_width, face_height);
-            view_group.append(repr_group);
-
-            if let Some(legend_group) = repr.legend_svg() {
-                view_group.append(legend_group.set(
-                    "transform",
-                    format!("translate({}, {})", legend_x, legend_y),
-                ));
-                legend_y -= 18.;
+            repr.draw(x_axis, y_axis, surface);
+        }
+        if let Some(y2_axis) = y2_axis {
+            for repr in &self.y2_representations {
+                repr.draw(x_axis, y2_axis, surface);
             }
         }
-
-        // Add in the axes
-        view_group.append(svg_render::draw_x_axis(&x_axis, face_width));
-        view_group.append(svg_render::draw_y_axis(&y_axis, face_height));
-
-        Ok(view_group)
     }
+}
 
-    /**
-    Create a text rendering of the view
-    */
-    fn to_text(&self, face_width: u32, face_height: u32) -> Result<String> {
-        let (x_axis, y_axis) = self.create_axes()?;
-
-        let (y_axis_string, longest_y_label_width) =
-            text_render::render_y_axis_strings(&y_axis, face_height);
-
-        let (x_axis_string, start_offset) = text_render::render_x_axis_strings(&x_axis, face_width);
-
-        let left_gutter_width = std::cmp::max(
-            longest_y_label_width as i32 + 3,
-            start_offset.wrapping_neg(),
-        ) as u32;
+impl Default for ContinuousView {
+    fn default() -> Self {
+        ContinuousView::new()
+    }
+}
 
-        let view_width = face_width + 1 + left_gutter_width + 1;
-        let view_height = face_height + 4;
+impl View for ContinuousView {
+    fn draw(&self, surface: &mut dyn Surface) -> Result<()> {
+        let (x_axis, y_axis, y2_axis) = self.create_axes()?;
+        let (face_width, face_height) = surface.size();
 
-        let blank: Vec<String> = (0..view_height)
-            .map(|_| (0..view_width).map(|_| ' ').collect())
-            .collect();
-        let mut view_string = blank.join("\n");
+        if let Some(grid) = &self.grid {
+            surface.grid(grid, Some(&x_axis), &y_axis);
+        }
 
-        for repr in &self.representations {
-            let face_string = repr.to_text(&x_axis, &y_axis, face_width, face_height);
-            view_string =
-                text_render::overlay(&view_string, &face_string, left_gutter_width as i32 + 1, 0);
+        // Then, based on those ranges, draw each repr, cut off at the edges of the face
+        surface.push_clip((0., 0.), (face_width, face_height));
+        self.draw_representations(&x_axis, &y_axis, y2_axis.as_ref(), surface);
+        surface.pop_clip();
+
+        let legend_entries = self.legend_entries();
+        if !legend_entries.is_empty() {
+            let origin = legend_origin(
+                &self.legend,
+                &legend_entries,
+                right_axis_width(&x_axis, y2_axis.as_ref(), face_width),
+                x_axis.get_label(),
+                (face_width, face_height),
+                |footprint| {
+                    self.draw_representations(&x_axis, &y_axis, y2_axis.as_ref(), footprint)
+                },
+            );
+            surface.legend(&self.legend, &legend_entries, origin);
         }
 
-        let view_string = text_render::overlay(
-            &view_string,
-            &y_axis_string,
-            left_gutter_width as i32 - 2 - longest_y_label_width,
-            0,
-        );
-        let view_string = text_render::overlay(
-            &view_string,
-            &x_axis_string,
-            left_gutter_width as i32,
-            face_height as i32,
-        );
+        // Add in the axes
+        surface.axis(&x_axis, AxisSide::Bottom);
+        surface.axis(&y_axis, AxisSide::Left);
+        if let Some(ref title) = self.title {
+            surface.title(title);
+        }
+        if let Some(ref y2_axis) = y2_axis {
+            surface.axis(y2_axis, AxisSide::Right);
+        }
 
-        Ok(view_string)
+        Ok(())
     }
 
     fn add_grid(&mut self, grid: Grid) {
@@ -250,9 +640,52 @@ impl View for ContinuousView {
     fn grid(&self) -> &Option<Grid> {
         &self.grid
     }
+
+    fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /**
+    Measure the tick labels and axis labels to find the space needed around the face.
+
+    The tick labels at the ends of the x-axis may reach past the face,
+    in which case the margins on either side are widened to fit them.
+    A legend placed outside of the face is given room beyond the axes.
+    */
+    fn margins(&self, width: f64) -> Result<Margins> {
+        let (x_axis, y_axis, y2_axis) = self.create_axes()?;
+        let (legend_width, legend_height) =
+            outside_legend_space(&self.legend, &self.legend_entries());
+
+        let mut left = render::vertical_axis_width(&y_axis);
+        let face_width = width - left - legend_width - 10.;
+        let (overhang_left, _) = render::horizontal_axis_overhang(&x_axis, face_width);
+        left = left.max(overhang_left + 4.);
+        let right = right_axis_width(&x_axis, y2_axis.as_ref(), width - left - legend_width - 10.);
+
+        Ok(Margins::new(
+            left,
+            right + legend_width,
+            top_margin(&self.title),
+            render::horizontal_axis_height(x_axis.get_label()) + legend_height,
+        ))
+    }
+}
+
+/// Which way the categories of a `CategoricalView` run
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Orientation {
+    /// Categories along the bottom, with the values increasing upwards
+    #[default]
+    Vertical,
+    /// Categories down the left-hand side from the top, with the values increasing to the right
+    Horizontal,
 }
 
 /// A view with categorical entries along the x-axis and continuous values along the y-axis
+///
+/// With a horizontal orientation the axes are drawn the other way around,
+/// but the `x_` methods still refer to the categories and the `y_` methods to the values.
 #[derive(Default)]
 pub struct CategoricalView {
     representations: Vec<Box<dyn CategoricalRepresentation>>,
@@ -260,7 +693,10 @@ pub struct CategoricalView {
     y_range: Option<axis::Range>,
     x_label: Option<String>,
     y_label: Option<String>,
+    title: Option<String>,
+    legend: Legend,
     grid: Option<Grid>,
+    orientation: Orientation,
 }
 
 impl CategoricalView {
@@ -274,7 +710,10 @@ impl CategoricalView {
             y_range: None,
             x_label: None,
             y_label: None,
+            title: None,
+            legend: Legend::new(),
             grid: None,
+            orientation: Orientation::Vertical,
         }
     }
 
@@ -324,6 +763,67 @@ impl CategoricalView {
         self
     }
 
+    /**
+    Set the title shown above the view
+    */
+    pub fn title<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.title = Some(value.into());
+        self
+    }
+
+    /**
+    Set where the legend is placed and how it looks
+    */
+    pub fn legend(mut self, legend: Legend) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /**
+    Set whether the categories run along the bottom of the view or down its side
+    */
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn get_orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Draw all of the representations, turning them when the view is horizontal
+    fn draw_representations(
+        &self,
+        x_axis: &axis::CategoricalAxis,
+        y_axis: &axis::ContinuousAxis,
+        surface: &mut dyn Surface,
+    ) {
+        match self.orientation {
+            Orientation::Vertical => {
+                for repr in &self.representations {
+                    repr.draw(x_axis, y_axis, surface);
+                }
+            }
+            Orientation::Horizontal => {
+                let mut surface = render::HorizontalSurface::new(surface);
+                for repr in &self.representations {
+                    repr.draw(x_axis, y_axis, &mut surface);
+                }
+            }
+        }
+    }
+
+    /// The legend entries of all the representations which have one
+    fn legend_entries(&self) -> Vec<render::LegendEntry> {
+        self.representations
+            .iter()
+            .flat_map(|repr| repr.legend_entries())
+            .collect()
+    }
+
     fn default_x_ticks(&self) -> Vec<String> {
         let mut v = vec![];
         for repr in &self.representations {
@@ -351,6 +851,10 @@ impl CategoricalView {
     }
 
     fn create_axes(&self) -> Result<(axis::CategoricalAxis, axis::ContinuousAxis)> {
+        for repr in &self.representations {
+            repr.check()?;
+        }
+
         let default_x_ticks = self.default_x_ticks();
         let x_range = self.x_range.as_ref().unwrap_or(&default_x_ticks);
 
@@ -379,34 +883,62 @@ impl CategoricalView {
 }
 
 impl View for CategoricalView {
-    fn to_svg(&self, face_width: f64, face_height: f64) -> Result<svg::node::element::Group> {
-        let mut view_group = svg::node::element::Group::new();
-
+    fn draw(&self, surface: &mut dyn Surface) -> Result<()> {
         let (x_axis, y_axis) = self.create_axes()?;
+        let (face_width, face_height) = surface.size();
+        let horizontal = self.orientation == Orientation::Horizontal;
 
         if let Some(grid) = &self.grid {
-            view_group.append(svg_render::draw_grid(
-                GridType::HorizontalOnly(grid),
-                face_width,
-                face_height,
-            ));
+            if horizontal {
+                render::HorizontalSurface::new(&mut *surface).grid(grid, None, &y_axis);
+            } else {
+                surface.grid(grid, None, &y_axis);
+            }
         }
 
-        // Then, based on those ranges, draw each repr as an SVG
-        for repr in &self.representations {
-            let repr_group = repr.to_svg(&x_axis, &y_axis, face_width, face_height);
-            view_group.append(repr_group);
+        // Then, based on those ranges, draw each repr, cut off at the edges of the face
+        surface.push_clip((0., 0.), (face_width, face_height));
+        self.draw_representations(&x_axis, &y_axis, surface);
+        surface.pop_clip();
+
+        // The label of the axis along the bottom, which an outside legend goes beneath
+        let bottom_label = if horizontal {
+            y_axis.get_label()
+        } else {
+            x_axis.get_label()
+        };
+        let right_width = if horizontal {
+            right_axis_width(&y_axis, None, face_width)
+        } else {
+            10.
+        };
+
+        let legend_entries = self.legend_entries();
+        if !legend_entries.is_empty() {
+            let origin = legend_origin(
+                &self.legend,
+                &legend_entries,
+                right_width,
+                bottom_label,
+                (face_width, face_height),
+                |footprint| self.draw_representations(&x_axis, &y_axis, footprint),
+            );
+            surface.legend(&self.legend, &legend_entries, origin);
         }
 
         // Add in the axes
-        view_group.append(svg_render::draw_categorical_x_axis(&x_axis, face_width));
-        view_group.append(svg_render::draw_y_axis(&y_axis, face_height));
-
-        Ok(view_group)
-    }
+        if horizontal {
+            surface.axis(&y_axis, AxisSide::Bottom);
+            surface.categorical_axis(&x_axis, AxisSide::Left);
+        } else {
+            surface.categorical_axis(&x_axis, AxisSide::Bottom);
+            surface.axis(&y_axis, AxisSide::Left);
+        }
+        if let Some(ref title) = self.title {
+            surface.title(title);
+        }
 
-    fn to_text(&self, _face_width: u32, _face_height: u32) -> Result<String> {
-        Ok("".into())
+        Ok(())
     }
 
     fn add_grid(&mut self, grid: Grid) {
@@ -416,6 +948,41 @@ impl View for CategoricalView {
     fn grid(&self) -> &Option<Grid> {
         &self.grid
     }
+
+    fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /**
+    Measure the y-axis tick labels and the axis labels to find the space needed around the face.
+
+    A legend placed outside of the face is given room beyond the axes.
+    */
+    fn margins(&self, width: f64) -> Result<Margins> {
+        let (x_axis, y_axis) = self.create_axes()?;
+        let (legend_width, legend_height) =
+            outside_legend_space(&self.legend, &self.legend_entries());
+        let top = top_margin(&self.title);
+        match self.orientation {
+            Orientation::Vertical => Ok(Margins::new(
+                render::vertical_axis_width(&y_axis),
+                10. + legend_width,
+                top,
+                render::horizontal_axis_height(x_axis.get_label()) + legend_height,
+            )),
+            Orientation::Horizontal => {
+                let left = render::vertical_categorical_axis_width(&x_axis);
+                // The value axis runs along the bottom, and its last tick label may reach past the face
+                let face_width = width - left - 10. - legend_width;
+                Ok(Margins::new(
+                    left,
+                    right_axis_width(&y_axis, None, face_width) + legend_width,
+                    top,
+                    render::horizontal_axis_height(y_axis.get_label()) + legend_height,
+                ))
+            }
+        }
+    }
 }
 
 /*pub struct AnyView<'a> {
@@ -0,0 +1,39 @@
+//! Helpers shared by the integration tests, for picking values out of rendered SVG
+
+#![allow(dead_code)]
+
+/// The value of a numeric attribute of a single element
+pub fn element_attribute(element: &str, name: &str) -> f64 {
+    let start = element.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+    element[start..start + element[start..].find('"').unwrap()]
+        .parse()
+        .unwrap()
+}
+
+/// Each element starting with `prefix`, up to the end of its opening tag
+pub fn elements<'a>(svg: &'a str, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+    svg.match_indices(prefix).map(move |(start, _)| {
+        let element = &svg[start..];
+        &element[..element.find('>').unwrap()]
+    })
+}
+
+/// The value of a numeric attribute of the first element starting with `prefix`
+pub fn attribute(svg: &str, prefix: &str, name: &str) -> f64 {
+    element_attribute(elements(svg, prefix).next().unwrap(), name)
+}
+
+/// The opening tag of the text element containing `label`
+pub fn text_element<'a>(svg: &'a str, label: &str) -> &'a str {
+    let end = svg.find(&format!("\n{}\n", label)).unwrap();
+    &svg[svg[..end].rfind("<text").unwrap()..end]
+}
+
+/// The x and y position of the text element containing `label`
+pub fn text_position(svg: &str, label: &str) -> (f64, f64) {
+    let element = text_element(svg, label);
+    (
+        element_attribute(element, "x"),
+        element_attribute(element, "y"),
+    )
+}
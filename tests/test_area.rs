@@ -0,0 +1,49 @@
+use plotlib::page::Page;
+use plotlib::repr::{Area, ContinuousRepresentation};
+use plotlib::style::{BoxStyle, LineStyle};
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_area_range() {
+    // The area under a curve reaches down to zero
+    let a = Area::new(vec![(1., 2.), (2., 4.), (3., 3.)]);
+    assert_eq!(a.range(0), (1., 3.));
+    assert_eq!(a.range(1), (0., 4.));
+    assert_eq!(a.baseline(5.).range(1), (2., 5.));
+
+    let a = Area::between(
+        vec![(0., 3.), (2., 4.)],
+        vec![(0., 1.), (1., 2.5), (3., 2.)],
+    );
+    assert_eq!(a.range(0), (0., 3.));
+    assert_eq!(a.range(1), (1., 4.));
+}
+
+#[test]
+fn test_area_svg() {
+    let a = Area::between(vec![(0., 3.), (2., 4.)], vec![(0., 1.), (2., 2.)])
+        .style(&BoxStyle::new().fill("steelblue").opacity(0.25))
+        .edge_style(LineStyle::new().colour("navy"))
+        .legend("band");
+    let v = ContinuousView::new().add(a);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+
+    assert_eq!(svg.matches("<polygon").count(), 1);
+    assert!(svg.contains("fill=\"steelblue\" fill-opacity=\"0.25\""));
+    // Both curves are outlined, and the legend swatch is too
+    assert_eq!(svg.matches("stroke=\"navy\"").count(), 3);
+    assert!(svg.contains("\nband\n"));
+}
+
+#[test]
+fn test_area_text() {
+    let a = Area::new(vec![(0., 2.), (10., 2.)]);
+    let v = ContinuousView::new()
+        .add(a)
+        .x_range(0., 10.)
+        .y_range(0., 4.);
+    let text = Page::single(&v).dimensions(20, 8).to_text().unwrap();
+    let shaded: Vec<usize> = text.lines().map(|line| line.matches('░').count()).collect();
+    // The rows of the face below the edge at y = 2 are shaded
+    assert_eq!(shaded.iter().filter(|&&count| count > 10).count(), 3);
+}
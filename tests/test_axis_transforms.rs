@@ -0,0 +1,110 @@
+use plotlib::axis::{FunctionTransform, Logit, Sqrt, SymLog, Time};
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_symlog_axis_crosses_zero() {
+    let p = Plot::new(vec![
+        (0., -5000.),
+        (1., -3.),
+        (2., 0.),
+        (3., 0.5),
+        (4., 80000.),
+    ])
+    .point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .y_transform(SymLog::new(10., 1.));
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    for label in &["\n-1000\n", "\n0\n", "\n1000\n"] {
+        assert!(svg.contains(label), "missing {:?} tick", label);
+    }
+    assert!(Page::single(&v).dimensions(60, 20).to_text().is_ok());
+}
+
+#[test]
+fn test_logit_axis() {
+    let p = Plot::new(vec![(1., 0.001), (2., 0.2), (3., 0.97), (4., 0.9995)])
+        .point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).y_transform(Logit);
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\n0.5\n"));
+    assert!(svg.contains("\n0.99\n"));
+
+    let p = Plot::new(vec![(1., 0.), (2., 0.5)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).y_transform(Logit);
+    assert!(Page::single(&v).to_svg().is_err());
+}
+
+#[test]
+fn test_logit_axis_rejects_values_of_one_or_more() {
+    let p = Plot::new(vec![(1.5, 0.2), (0.5, 0.4)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).x_transform(Logit);
+    let err = Page::single(&v).to_svg().unwrap_err();
+    assert!(err.to_string().contains("1.5"));
+
+    let p = Plot::new(vec![(1., 0.2), (2., 0.4)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .y_transform(Logit)
+        .y_range(0.1, 1.);
+    assert!(Page::single(&v).to_svg().is_err());
+}
+
+#[test]
+fn test_sqrt_and_function_axes() {
+    let p = Plot::new(vec![(1., 0.), (2., 25.), (3., 100.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).y_transform(Sqrt);
+    assert!(Page::single(&v).to_svg().is_ok());
+
+    let v = ContinuousView::new()
+        .add(Plot::new(vec![(1., 0.), (2., 25.)]).point_style(PointStyle::new()))
+        .y_transform(FunctionTransform::new(|y| y.powi(3), f64::cbrt));
+    assert!(Page::single(&v).to_svg().is_ok());
+}
+
+#[test]
+fn test_time_axis() {
+    // Every six hours over two days from 2023-11-14 00:00 UTC
+    let start = 1_699_920_000.;
+    let data: Vec<_> = (0..=8)
+        .map(|i| (start + f64::from(i) * 6. * 3600., f64::from(i)))
+        .collect();
+
+    let v = ContinuousView::new()
+        .add(Plot::new(data.clone()).point_style(PointStyle::new()))
+        .x_time();
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\n2023-11-15 00:00\n"));
+    assert!(!svg.contains("1699920000"));
+
+    let v = ContinuousView::new()
+        .add(Plot::new(data).point_style(PointStyle::new()))
+        .x_transform(Time::new().format("%d %b %Hh"));
+    let text = Page::single(&v).dimensions(80, 20).to_text().unwrap();
+    assert!(text.contains("15 Nov 00h"));
+}
+
+#[test]
+fn test_time_axis_single_value() {
+    // A single point at 2023-11-14 13:20 UTC is shown within its day, not from the epoch
+    let v = ContinuousView::new()
+        .add(Plot::new(vec![(1_699_968_000., 1.)]).point_style(PointStyle::new()))
+        .x_time();
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\n2023-11-14"));
+    assert!(!svg.contains("1970"));
+}
+
+#[test]
+fn test_time_axis_out_of_calendar() {
+    let v = ContinuousView::new()
+        .add(Plot::new(vec![(0., 0.), (1e300, 1.)]).point_style(PointStyle::new()))
+        .x_time();
+    let err = Page::single(&v).to_svg().unwrap_err();
+    assert!(err.to_string().contains("outside the domain"), "{}", err);
+}
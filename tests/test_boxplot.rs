@@ -0,0 +1,80 @@
+use plotlib::page::Page;
+use plotlib::repr::{BoxPlot, CategoricalRepresentation, WhiskerRule};
+use plotlib::style::PointStyle;
+use plotlib::view::CategoricalView;
+
+fn latencies() -> Vec<f64> {
+    vec![
+        3., 12., 14., 15., 15., 16., 18., 19., 22., 24., 26., 30., 48., 95.,
+    ]
+}
+
+/// The SVG of a view holding just the box plot
+fn svg(b: BoxPlot<'static>) -> String {
+    let v = CategoricalView::new().add(b.label("b"));
+    Page::single(&v).to_svg().unwrap().to_string()
+}
+
+fn outlier_count(rule: WhiskerRule) -> usize {
+    let b = BoxPlot::from_vec(latencies())
+        .whiskers(rule)
+        .outlier_style(PointStyle::new().colour("red"));
+    svg(b).matches("<circle").count()
+}
+
+#[test]
+fn test_whisker_rules() {
+    // Min/max whiskers leave nothing beyond them
+    assert_eq!(
+        BoxPlot::from_vec(latencies()).get_whiskers(),
+        WhiskerRule::MinMax
+    );
+    assert_eq!(outlier_count(WhiskerRule::MinMax), 0);
+    // The quartiles are 15 and 26, so only 48 and 95 are beyond 1.5 times the range
+    assert_eq!(outlier_count(WhiskerRule::Tukey), 2);
+    // The 10th and 90th percentiles are 12.6 and 42.6, leaving two values beyond each
+    assert_eq!(outlier_count(WhiskerRule::Percentiles(10., 90.)), 4);
+}
+
+#[test]
+fn test_whisker_percentiles_outside_box() {
+    // Reversed, inside the box, or outside of 0 to 100
+    let reversed_or_inside = [
+        (90., 10.),
+        (40., 60.),
+        (-5., 90.),
+        (10., 101.),
+        (f64::NAN, 90.),
+    ];
+    for (lower, upper) in reversed_or_inside {
+        let b = BoxPlot::from_vec(latencies()).whiskers(WhiskerRule::Percentiles(lower, upper));
+        let v = CategoricalView::new().add(b.label("b"));
+        let err = Page::single(&v).to_svg().unwrap_err();
+        assert!(err.to_string().contains("percentiles"), "{}", err);
+    }
+    // The edges of the box itself are allowed
+    let b = BoxPlot::from_vec(latencies()).whiskers(WhiskerRule::Percentiles(25., 75.));
+    let v = CategoricalView::new().add(b.label("b"));
+    assert!(Page::single(&v).to_svg().is_ok());
+}
+
+#[test]
+fn test_outliers_within_range() {
+    let b = BoxPlot::from_vec(latencies()).whiskers(WhiskerRule::Tukey);
+    assert_eq!(b.range(), (3., 95.));
+}
+
+#[test]
+fn test_mean_and_notch() {
+    let plain = svg(BoxPlot::from_vec(latencies()));
+    assert!(!plain.contains("<polygon"));
+
+    let b = BoxPlot::from_vec(latencies())
+        .mean_style(PointStyle::new().colour("green"))
+        .notched(true);
+    assert!(b.is_notched());
+    let decorated = svg(b);
+    assert_eq!(decorated.matches("fill=\"green\"").count(), 1);
+    // The notched box is drawn as a polygon rather than a rectangle
+    assert_eq!(decorated.matches("<polygon").count(), 1);
+}
@@ -0,0 +1,88 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::{ContinuousView, View};
+
+fn zoomed_view(plot: Plot) -> ContinuousView {
+    ContinuousView::new()
+        .add(plot)
+        .x_range(0., 5.)
+        .y_range(0., 5.)
+}
+
+#[test]
+fn test_svg_clipped_to_face() {
+    let p = Plot::new(vec![(1., 1.), (4., 4.), (8., 8.), (-3., 2.)])
+        .point_style(PointStyle::new())
+        .line_style(LineStyle::new());
+    let svg = Page::single(&zoomed_view(p)).to_svg().unwrap().to_string();
+    assert!(svg.contains("<clipPath"));
+    assert!(svg.contains("clip-path=\"url(#"));
+    // Only the markers within the axes are drawn
+    assert_eq!(svg.matches("<circle").count(), 2);
+}
+
+#[test]
+fn test_svg_clip_ids() {
+    let plot = || Plot::new(vec![(1., 1.), (4., 4.)]).point_style(PointStyle::new());
+    let (first, second) = (zoomed_view(plot()), zoomed_view(plot()));
+    let page = Page::empty().add_plot(&first).add_plot(&second);
+    let svg = page.to_svg().unwrap().to_string();
+
+    // Each view has its own clip region, and the ids do not depend on what was rendered before
+    assert!(svg.contains("id=\"plotlib-clip-0\""));
+    assert!(svg.contains("id=\"plotlib-clip-1\""));
+    assert_eq!(page.to_svg().unwrap().to_string(), svg);
+}
+
+#[test]
+fn test_standalone_svg_clip_ids() {
+    let plot = || Plot::new(vec![(1., 1.), (4., 4.)]).point_style(PointStyle::new());
+    let clip_id = |view: &ContinuousView| {
+        let group = view.to_svg(200., 100.).unwrap().to_string();
+        let start = group.find("id=\"").unwrap() + 4;
+        group[start..start + group[start..].find('"').unwrap()].to_string()
+    };
+
+    // Groups drawn separately can be put in one document without their clip regions clashing
+    let (first, second) = (clip_id(&zoomed_view(plot())), clip_id(&zoomed_view(plot())));
+    assert_ne!(first, second);
+    assert!(first.starts_with("plotlib-clip-"));
+}
+
+#[test]
+fn test_text_clipped_to_face() {
+    let p = Plot::new(vec![(1., 1.), (4., 4.), (8., 8.), (2., -3.)]).point_style(PointStyle::new());
+    let text = Page::single(&zoomed_view(p))
+        .dimensions(30, 10)
+        .to_text()
+        .unwrap();
+    assert_eq!(text.matches('●').count(), 2);
+
+    // The line is cut at the edge of the face, so nothing is drawn to the right of the x-axis
+    let p = Plot::new(vec![(0., 0.), (10., 10.)]).line_style(LineStyle::new());
+    let text = Page::single(&zoomed_view(p))
+        .dimensions(30, 10)
+        .to_text()
+        .unwrap();
+    let axis_end = text
+        .lines()
+        .find(|line| line.contains("+---"))
+        .unwrap()
+        .trim_end()
+        .chars()
+        .count();
+    for line in text.lines() {
+        assert!(line.trim_end().chars().count() <= axis_end, "{:?}", line);
+    }
+}
+
+#[test]
+fn test_text_long_line_clipped() {
+    // Only the part of the line inside the face is stepped along, however far off it the line runs
+    let p = Plot::new(vec![(1., 1.), (1e9, 2.)]).line_style(LineStyle::new());
+    let v = ContinuousView::new().add(p).x_range(0., 2.).y_range(0., 3.);
+    let text = Page::single(&v).dimensions(80, 30).to_text().unwrap();
+    let row = text.lines().find(|line| line.contains("1-|")).unwrap();
+    assert!(row.ends_with("-- "), "{:?}", row);
+}
@@ -0,0 +1,127 @@
+use plotlib::axis::{CategoricalAxis, ContinuousAxis};
+use plotlib::page::Page;
+use plotlib::render::{Surface, TextAnchor};
+use plotlib::repr::{CategoricalRepresentation, ContinuousRepresentation};
+use plotlib::style::{BoxStyle, LineStyle, PointStyle};
+use plotlib::view::{CategoricalView, ContinuousView};
+
+/// A horizontal line across the whole face at a fixed height
+struct HorizontalLine {
+    y: f64,
+}
+
+impl ContinuousRepresentation for HorizontalLine {
+    fn range(&self, dim: u32) -> (f64, f64) {
+        match dim {
+            0 => (0., 1.),
+            _ => (self.y - 1., self.y + 1.),
+        }
+    }
+
+    fn draw(&self, _x_axis: &ContinuousAxis, y_axis: &ContinuousAxis, surface: &mut dyn Surface) {
+        let (face_width, face_height) = surface.size();
+        let y = y_axis.value_to_face_offset(self.y, face_height);
+        surface.line((0., y), (face_width, y), &LineStyle::new());
+    }
+}
+
+/// A bar drawn across the full width given to its category
+struct WideBar {
+    label: String,
+    value: f64,
+}
+
+impl CategoricalRepresentation for WideBar {
+    fn range(&self) -> (f64, f64) {
+        (0., self.value)
+    }
+
+    fn ticks(&self) -> Vec<String> {
+        vec![self.label.clone()]
+    }
+
+    fn draw(&self, x_axis: &CategoricalAxis, y_axis: &ContinuousAxis, surface: &mut dyn Surface) {
+        let (face_width, face_height) = surface.size();
+        let centre = x_axis
+            .category_to_face_offset(&self.label, face_width)
+            .unwrap();
+        let width = x_axis.category_width(face_width);
+        let base = y_axis.value_to_face_offset(0., face_height);
+        let top = y_axis.value_to_face_offset(self.value, face_height);
+        surface.rect(
+            (centre - width / 2., base),
+            (width, top - base),
+            &BoxStyle::new(),
+        );
+    }
+}
+
+#[test]
+fn test_custom_continuous_representation() {
+    let v = ContinuousView::new().add(HorizontalLine { y: 2. });
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("<line"));
+
+    let text = Page::single(&v).dimensions(20, 10).to_text().unwrap();
+    assert!(text.contains("-----"));
+}
+
+#[test]
+fn test_custom_categorical_representation() {
+    let v = CategoricalView::new()
+        .add(WideBar {
+            label: "a".into(),
+            value: 3.,
+        })
+        .add(WideBar {
+            label: "b".into(),
+            value: 5.,
+        });
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    // Only count the drawn bars, not the rectangle the face is clipped to
+    assert_eq!(svg.matches("<rect fill=").count(), 2);
+}
+
+/// A surface which only keeps the paths drawn onto it
+#[derive(Default)]
+struct PathRecorder {
+    paths: Vec<(Vec<(f64, f64)>, String)>,
+}
+
+impl Surface for PathRecorder {
+    fn size(&self) -> (f64, f64) {
+        (100., 100.)
+    }
+
+    fn line(&mut self, _start: (f64, f64), _end: (f64, f64), _style: &LineStyle) {}
+
+    fn path(&mut self, points: &[(f64, f64)], style: &LineStyle) {
+        self.paths.push((points.to_vec(), style.get_colour()));
+    }
+
+    fn rect(&mut self, _origin: (f64, f64), _size: (f64, f64), _style: &BoxStyle) {}
+
+    fn text(&mut self, _position: (f64, f64), _text: &str, _anchor: TextAnchor, _font_size: f64) {}
+
+    fn marker(&mut self, _position: (f64, f64), _style: &PointStyle) {}
+
+    fn push_clip(&mut self, _origin: (f64, f64), _size: (f64, f64)) {}
+
+    fn pop_clip(&mut self) {}
+}
+
+#[test]
+fn test_custom_surface_outlines_polygons() {
+    let mut surface = PathRecorder::default();
+    let corners = [(0., 0.), (10., 0.), (5., 8.)];
+    surface.polygon(&corners, &BoxStyle::new().outline("teal"));
+    assert_eq!(
+        surface.paths,
+        vec![(
+            vec![(0., 0.), (10., 0.), (5., 8.), (0., 0.)],
+            "teal".to_string()
+        )]
+    );
+}
@@ -0,0 +1,69 @@
+use plotlib::page::Page;
+use plotlib::repr::{ContinuousRepresentation, Plot};
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn plot() -> Plot {
+    Plot::new(vec![(1., 2.), (2., 5.), (3., 4.)])
+        .point_style(PointStyle::new())
+        .y_errors_asymmetric(vec![(1., 0.5), (0.5, 2.), (1., 1.)])
+        .x_errors(vec![0.25, 0.25, 0.5])
+}
+
+#[test]
+fn test_error_bars_in_range() {
+    let p = plot();
+    assert_eq!(p.range(0), (0.75, 3.5));
+    assert_eq!(p.range(1), (1., 7.));
+
+    // Without error bars the range is just that of the points
+    assert_eq!(Plot::new(vec![(1., 2.), (2., 5.)]).range(1), (2., 5.));
+}
+
+#[test]
+fn test_error_bars_svg() {
+    let p = plot().error_style(LineStyle::new().colour("grey"));
+    let v = ContinuousView::new().add(p);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    // Each of the three points has an x and a y bar, each with two caps
+    assert_eq!(svg.matches("stroke=\"grey\"").count(), 3 * 2 * 3);
+
+    // Without caps there are just the bars
+    let p = plot()
+        .error_style(LineStyle::new().colour("grey"))
+        .error_cap_width(0.);
+    let v = ContinuousView::new().add(p);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert_eq!(svg.matches("stroke=\"grey\"").count(), 3 * 2);
+}
+
+#[test]
+fn test_error_bars_text() {
+    let p = Plot::new(vec![(1., 5.)]).y_errors(vec![2.]);
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(0., 2.)
+        .y_range(0., 10.);
+    let text = Page::single(&v).dimensions(20, 20).to_text().unwrap();
+    // A vertical bar with a cap at either end
+    let faces: Vec<&str> = text
+        .lines()
+        .filter_map(|line| line.split_once('|'))
+        .map(|(_, face)| face.trim())
+        .collect();
+    assert_eq!(faces.iter().filter(|&&face| face == "---").count(), 2);
+    assert_eq!(faces.iter().filter(|&&face| face == "|").count(), 7);
+}
+
+#[test]
+fn test_error_bars_must_match_points() {
+    let p = Plot::new(vec![(1., 2.), (2., 5.), (3., 4.)])
+        .point_style(PointStyle::new())
+        .y_errors(vec![0.5, 1.]);
+    let v = ContinuousView::new().add(p);
+    let err = Page::single(&v).to_svg().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("2 \"y\" error bars for 3 data points"));
+    assert!(Page::single(&v).to_text().is_err());
+}
@@ -0,0 +1,55 @@
+use plotlib::grid::Grid;
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::{ContinuousView, View};
+
+fn view() -> ContinuousView {
+    let p = Plot::new(vec![(0., 0.), (10., 4.)]).point_style(PointStyle::new());
+    ContinuousView::new()
+        .add(p)
+        .x_range(0., 10.)
+        .y_range(0., 4.)
+        .x_ticks(&[0., 5., 10.])
+        .y_ticks(&[0., 2., 4.])
+        .x_minor_ticks(5)
+}
+
+#[test]
+fn test_grid_at_ticks() {
+    let plain = view();
+    let mut major = view();
+    major.add_grid(Grid::at_ticks());
+    let mut all = view();
+    all.add_grid(
+        Grid::at_ticks()
+            .include_minor(true)
+            .minor_style(LineStyle::new().dash(&[2., 3.]).opacity(0.25)),
+    );
+
+    let count_lines = |v: &ContinuousView| {
+        Page::single(v)
+            .to_svg()
+            .unwrap()
+            .to_string()
+            .matches("<line")
+            .count()
+    };
+    // Three vertical and three horizontal major lines, and eight vertical minor lines
+    assert_eq!(count_lines(&major), count_lines(&plain) + 6);
+    assert_eq!(count_lines(&all), count_lines(&plain) + 14);
+
+    let svg = Page::single(&all).to_svg().unwrap().to_string();
+    assert!(svg.contains(r#"stroke-dasharray="2 3""#));
+    assert!(svg.contains(r#"stroke-opacity="0.25""#));
+}
+
+#[test]
+fn test_text_grid() {
+    let mut v = view();
+    v.add_grid(Grid::at_ticks().include_minor(true));
+    let text = Page::single(&v).dimensions(40, 8).to_text().unwrap();
+    assert!(text.contains('─'));
+    assert!(text.contains('┊'));
+    assert!(text.contains('┼'));
+}
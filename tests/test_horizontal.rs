@@ -0,0 +1,87 @@
+mod common;
+
+use common::{attribute, text_element};
+use plotlib::page::Page;
+use plotlib::repr::BarChart;
+use plotlib::style::BoxStyle;
+use plotlib::view::{CategoricalView, Orientation, View};
+
+fn view(orientation: Orientation) -> CategoricalView {
+    CategoricalView::new()
+        .add(
+            BarChart::new(4.)
+                .label("first")
+                .style(&BoxStyle::new().fill("red")),
+        )
+        .add(
+            BarChart::new(2.)
+                .label("a rather long second category")
+                .style(&BoxStyle::new().fill("blue")),
+        )
+        .y_range(0., 4.)
+        .orientation(orientation)
+}
+
+#[test]
+fn test_horizontal_bars() {
+    let v = view(Orientation::Horizontal);
+    assert_eq!(v.get_orientation(), Orientation::Horizontal);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+
+    // The bars run to the right, with the longer value reaching further
+    let red_width = attribute(&svg, "<rect fill=\"red\"", "width");
+    let blue_width = attribute(&svg, "<rect fill=\"blue\"", "width");
+    assert!(red_width > attribute(&svg, "<rect fill=\"red\"", "height"));
+    assert!((red_width - 2. * blue_width).abs() < 1e-6);
+
+    // The first category is at the top, and both start at the axis on the left
+    assert!(
+        attribute(&svg, "<rect fill=\"red\"", "y") < attribute(&svg, "<rect fill=\"blue\"", "y")
+    );
+    assert_eq!(attribute(&svg, "<rect fill=\"red\"", "x"), 0.);
+    assert_eq!(attribute(&svg, "<rect fill=\"blue\"", "x"), 0.);
+
+    // The category labels are on the left of the face
+    assert!(svg.contains("text-anchor=\"end\""));
+    assert!(svg.contains("\na rather long second category\n"));
+}
+
+#[test]
+fn test_horizontal_margins() {
+    let vertical = view(Orientation::Vertical).margins(600.).unwrap();
+    let horizontal = view(Orientation::Horizontal).margins(600.).unwrap();
+    // The long category label needs room beside the face, rather than below it
+    assert!(horizontal.left > vertical.left + 50.);
+    assert!(horizontal.bottom < 40.);
+}
+
+#[test]
+fn test_horizontal_axis_label() {
+    let v = view(Orientation::Horizontal).x_label("Team");
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    // The category axis label is rotated up the left, at the same size as other axis labels
+    let element = text_element(&svg, "Team");
+    assert!(element.contains("font-size=\"12\""));
+    assert!(element.contains("rotate(-90"));
+}
+
+#[test]
+fn test_categorical_text() {
+    for orientation in [Orientation::Vertical, Orientation::Horizontal] {
+        let text = Page::single(&view(orientation))
+            .dimensions(70, 12)
+            .to_text()
+            .unwrap();
+        assert!(text.contains("first"), "{}", text);
+        assert!(text.contains("a rather long second category"), "{}", text);
+        assert!(text.contains("+----"), "{}", text);
+    }
+
+    // Down the side, the first category is on the line above the second
+    let text = Page::single(&view(Orientation::Horizontal))
+        .dimensions(70, 12)
+        .to_text()
+        .unwrap();
+    let line = |label: &str| text.lines().position(|l| l.contains(label)).unwrap();
+    assert!(line("first-|") < line("a rather long second category-|"));
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::text_position;
+use plotlib::legend::{Legend, LegendPosition};
+use plotlib::page::Page;
+use plotlib::repr::{BarChart, BoxPlot, Histogram, HistogramBins, Plot};
+use plotlib::style::{BoxStyle, LineStyle, PointStyle};
+use plotlib::view::{CategoricalView, ContinuousView, View};
+
+fn plot(data: Vec<(f64, f64)>, name: &str) -> Plot {
+    Plot::new(data)
+        .point_style(PointStyle::new())
+        .legend(name.to_string())
+}
+
+#[test]
+fn test_legend_frame_and_title() {
+    let v = ContinuousView::new()
+        .add(plot(vec![(0., 0.), (1., 1.)], "data"))
+        .legend(
+            Legend::new()
+                .title("Key")
+                .frame(LineStyle::new().colour("grey"))
+                .background("white"),
+        );
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\nKey\n"));
+    assert!(svg.contains("fill=\"white\""));
+    assert!(svg.contains("stroke=\"none\""));
+    // The title is above the entries
+    assert!(text_position(&svg, "Key").1 < text_position(&svg, "data").1);
+
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[lines.len() - 2].trim(), "Key");
+    assert_eq!(lines[lines.len() - 1].trim(), "●  data");
+}
+
+#[test]
+fn test_legend_columns() {
+    let v = ContinuousView::new()
+        .add(plot(vec![(0., 0.), (1., 1.)], "first"))
+        .add(plot(vec![(0., 1.), (1., 0.)], "second"))
+        .legend(Legend::new().columns(2));
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    let first = text_position(&svg, "first");
+    let second = text_position(&svg, "second");
+    assert_eq!(first.1, second.1);
+    assert!(first.0 < second.0);
+}
+
+#[test]
+fn test_legend_uses_requested_columns() {
+    let v = ContinuousView::new()
+        .add(plot(vec![(0., 0.)], "a"))
+        .add(plot(vec![(1., 1.)], "b"))
+        .add(plot(vec![(2., 2.)], "c"))
+        .add(plot(vec![(3., 3.)], "d"))
+        .legend(Legend::new().columns(3));
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    let [a, b, c, d] = ["a", "b", "c", "d"].map(|label| text_position(&svg, label));
+    // The first column takes the extra entry, and the others one each
+    assert_eq!(a.0, b.0);
+    assert!(a.1 < b.1);
+    assert!(b.0 < c.0 && c.0 < d.0);
+    assert_eq!(a.1, c.1);
+    assert_eq!(a.1, d.1);
+}
+
+#[test]
+fn test_best_legend_avoids_data() {
+    // Data across the top and in the bottom right leaves the bottom left free
+    let data = vec![(0., 9.), (10., 9.), (9., 1.), (8., 2.)];
+    let v = ContinuousView::new()
+        .add(plot(data, "data").line_style(LineStyle::new()))
+        .x_range(0., 10.)
+        .y_range(0., 10.)
+        .legend(Legend::new().position(LegendPosition::Best));
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    let (x, y) = text_position(&svg, "data");
+    assert!(x < 100.);
+    assert!(y > -100.);
+}
+
+#[test]
+fn test_outside_legend_reserves_space() {
+    let v = || ContinuousView::new().add(plot(vec![(0., 0.), (1., 1.)], "a long legend entry"));
+    let inside = v().margins(600.).unwrap();
+
+    let right = v().legend(Legend::new().position(LegendPosition::OutsideRight));
+    let margins = right.margins(600.).unwrap();
+    assert!(margins.right > inside.right + 80.);
+    assert_eq!(margins.bottom, inside.bottom);
+    // The legend is drawn in the space beyond the face
+    let svg = Page::single(&right).to_svg().unwrap().to_string();
+    let face_width = 600. - margins.left - margins.right;
+    assert!(text_position(&svg, "a long legend entry").0 > face_width);
+
+    let bottom = v().legend(Legend::new().position(LegendPosition::OutsideBottom));
+    let margins = bottom.margins(600.).unwrap();
+    assert!(margins.bottom > inside.bottom + 20.);
+    assert_eq!(margins.right, inside.right);
+    let svg = Page::single(&bottom).to_svg().unwrap().to_string();
+    assert!(text_position(&svg, "a long legend entry").1 > 0.);
+}
+
+#[test]
+fn test_histogram_legend() {
+    let h1 = Histogram::from_slice(&[1., 2., 2., 3.], HistogramBins::Count(3))
+        .style(&BoxStyle::new().fill("burlywood"))
+        .legend("before");
+    let h2 = Histogram::from_slice(&[1., 1., 2., 3.], HistogramBins::Count(3))
+        .style(&BoxStyle::new().fill("steelblue"))
+        .legend("after");
+    let v = ContinuousView::new().add(h1).add(h2);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\nbefore\n"));
+    assert!(svg.contains("\nafter\n"));
+    // Each entry has a swatch filled with the colour of its bars
+    let swatch = |fill: &str| format!("fill=\"{}\" height=\"9\"", fill);
+    assert!(svg.contains(&swatch("burlywood")));
+    assert!(svg.contains(&swatch("steelblue")));
+}
+
+#[test]
+fn test_categorical_legend() {
+    let b1 = BarChart::new(5.3)
+        .label("1")
+        .style(&BoxStyle::new().fill("darkolivegreen"))
+        .legend("bar");
+    let b2 = BoxPlot::from_vec(vec![1., 2., 3., 4.])
+        .label("2")
+        .style(&BoxStyle::new().fill("steelblue"))
+        .legend("box");
+    let b3 = BarChart::new(2.).label("3");
+    let v = CategoricalView::new()
+        .add(b1)
+        .add(b2)
+        .add(b3)
+        .legend(Legend::new().position(LegendPosition::OutsideRight));
+
+    let inside = CategoricalView::new()
+        .add(BarChart::new(5.3).label("1"))
+        .margins(600.)
+        .unwrap();
+    assert!(v.margins(600.).unwrap().right > inside.right);
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\nbar\n"));
+    assert!(svg.contains("\nbox\n"));
+    assert!(svg.contains("fill=\"darkolivegreen\" height=\"9\""));
+    // Only the representations with a legend are listed
+    assert_eq!(svg.matches("height=\"9\"").count(), 2);
+}
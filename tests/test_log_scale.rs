@@ -0,0 +1,59 @@
+use plotlib::page::Page;
+use plotlib::repr::{Histogram, HistogramBins, Plot};
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_log_axis_labels_decades() {
+    let p = Plot::new(vec![(1., 3.), (10., 40.), (100., 2000.), (1000., 90000.)])
+        .point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_log(10.)
+        .y_log(10.)
+        .x_range(0.5, 2000.)
+        .y_range(1., 1e5);
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    for label in &["\n1\n", "\n10\n", "\n100\n", "\n1000\n", "\n10^4\n"] {
+        assert!(svg.contains(label), "missing {:?} tick", label);
+    }
+
+    let text = Page::single(&v).dimensions(60, 20).to_text().unwrap();
+    assert!(text.contains("1000"));
+    assert_eq!(text.matches('●').count(), 4);
+}
+
+#[test]
+fn test_log_histogram() {
+    let data = [1., 2., 2., 5., 5., 5., 30., 70.];
+    let h = Histogram::from_slice(&data, HistogramBins::Count(4));
+    let v = ContinuousView::new().add(h).y_log(2.);
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("<rect"));
+    assert!(svg.contains("\n4\n"));
+    assert!(!svg.contains("NaN") && !svg.contains("inf"));
+}
+
+#[test]
+fn test_log_axis_rejects_non_positive_data() {
+    let p = Plot::new(vec![(-1., 3.), (10., 40.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).x_log(10.);
+    let err = Page::single(&v).to_svg().unwrap_err();
+    assert!(err.to_string().contains("-1"));
+
+    let p = Plot::new(vec![(1., 3.), (10., 40.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).y_log(10.).y_range(0., 100.);
+    assert!(Page::single(&v).to_text().is_err());
+}
+
+#[test]
+fn test_log_axis_rejects_invalid_base() {
+    for base in [0., -2., 1., f64::NAN, f64::INFINITY] {
+        let p = Plot::new(vec![(1., 3.), (10., 40.)]).point_style(PointStyle::new());
+        let v = ContinuousView::new().add(p).x_log(base);
+        let err = Page::single(&v).to_svg().unwrap_err();
+        assert!(err.to_string().contains("base"), "{}", err);
+    }
+}
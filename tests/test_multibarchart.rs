@@ -0,0 +1,69 @@
+mod common;
+
+use plotlib::page::Page;
+use plotlib::repr::{BarMode, BarSeries, CategoricalRepresentation, MultiBarChart};
+use plotlib::style::BoxStyle;
+use plotlib::view::CategoricalView;
+
+fn chart() -> MultiBarChart {
+    MultiBarChart::new(vec!["a", "b"])
+        .series(
+            BarSeries::new(vec![4., 2.])
+                .style(&BoxStyle::new().fill("steelblue"))
+                .legend("CPU"),
+        )
+        .series(
+            BarSeries::new(vec![1., -3.])
+                .style(&BoxStyle::new().fill("darkorange"))
+                .legend("Memory"),
+        )
+        .series(BarSeries::new(vec![2.5]).style(&BoxStyle::new().fill("seagreen")))
+}
+
+#[test]
+fn test_multibarchart_range() {
+    let grouped = chart();
+    assert_eq!(grouped.get_mode(), BarMode::Grouped);
+    assert_eq!(grouped.range(), (-3., 4.));
+    assert_eq!(grouped.ticks(), vec!["a".to_string(), "b".to_string()]);
+
+    // Stacked bars reach up to the total of the positive values
+    // and down to the total of the negative values
+    let stacked = chart().mode(BarMode::Stacked);
+    assert_eq!(stacked.range(), (-3., 7.5));
+}
+
+#[test]
+fn test_multibarchart_svg() {
+    for mode in [BarMode::Grouped, BarMode::Stacked] {
+        let v = CategoricalView::new().add(chart().mode(mode));
+        let svg = Page::single(&v).to_svg().unwrap().to_string();
+
+        // A bar for each value, and a swatch for each series with a legend
+        assert_eq!(svg.matches("<rect fill=\"steelblue\"").count(), 3);
+        assert_eq!(svg.matches("<rect fill=\"darkorange\"").count(), 3);
+        assert_eq!(svg.matches("<rect fill=\"seagreen\"").count(), 1);
+        assert!(svg.contains("\nCPU\n"));
+        assert!(svg.contains("\nMemory\n"));
+    }
+}
+
+#[test]
+fn test_multibarchart_stacked_segments() {
+    let b = MultiBarChart::new(vec!["a"])
+        .series(BarSeries::new(vec![2.]).style(&BoxStyle::new().fill("red")))
+        .series(BarSeries::new(vec![2.]).style(&BoxStyle::new().fill("blue")))
+        .mode(BarMode::Stacked);
+    let v = CategoricalView::new().add(b).y_range(0., 4.);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+
+    let attribute = |colour: &str, name: &str| {
+        common::attribute(&svg, &format!("<rect fill=\"{}\"", colour), name)
+    };
+    // The segments are the same height, and the second sits on top of the first
+    assert_eq!(attribute("red", "height"), attribute("blue", "height"));
+    assert_eq!(attribute("red", "x"), attribute("blue", "x"));
+    assert!(
+        (attribute("red", "y") - attribute("blue", "y") - attribute("blue", "height")).abs() < 1e-6
+    );
+}
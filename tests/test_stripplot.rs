@@ -0,0 +1,116 @@
+mod common;
+
+use common::{element_attribute, elements};
+use plotlib::page::Page;
+use plotlib::repr::{BoxPlot, StripLayout, StripPlot};
+use plotlib::style::{PointMarker, PointStyle};
+use plotlib::view::CategoricalView;
+
+fn runs() -> Vec<f64> {
+    (0..30)
+        .map(|i| 10. + (f64::from(i) * 1.7).sin() * 2.)
+        .collect()
+}
+
+fn svg(s: StripPlot) -> String {
+    let v = CategoricalView::new()
+        .add(BoxPlot::from_vec(runs()).label("a"))
+        .add(s.label("a").style(PointStyle::new().colour("red")));
+    Page::single(&v).to_svg().unwrap().to_string()
+}
+
+/// The centres of all of the circles in some SVG
+fn circle_centres(svg: &str) -> Vec<(f64, f64)> {
+    elements(svg, "<circle")
+        .map(|element| {
+            (
+                element_attribute(element, "cx"),
+                element_attribute(element, "cy"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_jitter_is_seeded() {
+    let first = svg(StripPlot::from_vec(runs()).seed(1));
+    let centres = circle_centres(&first);
+    assert_eq!(
+        centres,
+        circle_centres(&svg(StripPlot::from_vec(runs()).seed(1)))
+    );
+    assert_ne!(
+        centres,
+        circle_centres(&svg(StripPlot::from_vec(runs()).seed(2)))
+    );
+    // A marker for every value, overlaid on the box plot of the same category
+    assert_eq!(centres.len(), 30);
+    assert_eq!(first.matches("\na\n").count(), 1);
+}
+
+#[test]
+fn test_jitter_width() {
+    let centres = circle_centres(&svg(
+        StripPlot::from_vec(runs()).layout(StripLayout::Jitter(0.))
+    ));
+    assert!(centres.iter().all(|&(x, _)| x == centres[0].0));
+
+    let centres = circle_centres(&svg(StripPlot::from_vec(runs())));
+    assert!(centres.iter().any(|&(x, _)| x != centres[0].0));
+}
+
+#[test]
+fn test_swarm_does_not_overlap() {
+    let s = StripPlot::from_vec(runs()).layout(StripLayout::Swarm);
+    assert_eq!(s.get_layout(), StripLayout::Swarm);
+    let centres = circle_centres(&svg(s));
+    assert_eq!(centres.len(), 30);
+    // The default markers have a radius of 3
+    for (i, &(x1, y1)) in centres.iter().enumerate() {
+        for &(x2, y2) in &centres[i + 1..] {
+            assert!(((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt() >= 6.);
+        }
+    }
+}
+
+#[test]
+fn test_swarm_leaves_out_nan() {
+    let s = StripPlot::from_vec(vec![1., f64::NAN, 2.]).layout(StripLayout::Swarm);
+    let v = CategoricalView::new().add(s.label("a"));
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert_eq!(circle_centres(&svg).len(), 2);
+}
+
+#[test]
+fn test_swarm_stays_in_category() {
+    // Far more equal values than fit side by side across a category
+    let swarm = |label: &str, marker| {
+        StripPlot::from_vec((0..200).map(|i| f64::from(i % 2)).collect())
+            .label(label)
+            .layout(StripLayout::Swarm)
+            .style(PointStyle::new().marker(marker))
+    };
+    let v = CategoricalView::new()
+        .add(swarm("a", PointMarker::Circle))
+        .add(swarm("b", PointMarker::Square));
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    let centres = circle_centres(&svg);
+    assert_eq!(centres.len(), 200);
+    // Leaving out the rectangle of the clip region
+    let squares: Vec<f64> = elements(&svg, "<rect")
+        .filter(|element| element.contains("fill="))
+        .map(|element| element_attribute(element, "x"))
+        .collect();
+    assert_eq!(squares.len(), 200);
+    let rightmost_circle = centres.iter().fold(f64::MIN, |m, &(x, _)| m.max(x));
+    assert!(squares.iter().all(|&x| x > rightmost_circle));
+
+    // In text, the markers are one character apart and each category keeps to its own half
+    let text = Page::single(&v).dimensions(80, 30).to_text().unwrap();
+    let row = text.lines().find(|line| line.contains('●')).unwrap();
+    let last_circle = row.rfind('●').unwrap();
+    let first_square = row.find('■').unwrap();
+    assert!(last_circle < first_square, "{:?}", row);
+    assert!(row.contains("●●●"));
+}
@@ -0,0 +1,34 @@
+use plotlib::axis::TickFormat;
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_default_tick_labels_are_short() {
+    let p = Plot::new(vec![(0., 0.), (0.3, 1.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p).x_range(0., 0.3);
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\n0.3\n"));
+    assert!(!svg.contains("0.30000000000000004"));
+}
+
+#[test]
+fn test_view_tick_format() {
+    let p = Plot::new(vec![(0., 0.), (3000., 1.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(0., 3000.)
+        .y_range(0., 1.)
+        .x_tick_format(TickFormat::SI)
+        .y_tick_format(TickFormat::Percent(0));
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\n1k\n"));
+    assert!(svg.contains("\n100%\n"));
+
+    let text = Page::single(&v).dimensions(60, 20).to_text().unwrap();
+    assert!(text.contains("3k"));
+    assert!(text.contains("100%"));
+}
@@ -0,0 +1,48 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::PointStyle;
+use plotlib::view::ContinuousView;
+
+#[test]
+fn test_explicit_ticks() {
+    let p = Plot::new(vec![(0., 120.), (2., 480.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new()
+        .add(p)
+        .x_range(0., 2.)
+        .y_range(0., 500.)
+        .x_labelled_ticks(&[(0., "Mon"), (1., "Tue"), (2., "Wed")])
+        .y_ticks(&[200., 300.]);
+
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(svg.contains("\nTue\n"));
+    assert!(svg.contains("\n300\n"));
+    assert!(!svg.contains("\n100\n"));
+
+    let text = Page::single(&v).dimensions(40, 10).to_text().unwrap();
+    assert!(text.contains("Tue"));
+    assert!(text.contains("300"));
+}
+
+#[test]
+fn test_minor_ticks() {
+    let p = Plot::new(vec![(0., 0.), (10., 10.)]).point_style(PointStyle::new());
+    let plain = ContinuousView::new().add(p.clone());
+    let minor = ContinuousView::new()
+        .add(p)
+        .x_minor_ticks(2)
+        .y_minor_ticks(2);
+
+    let count_lines = |v: &ContinuousView| {
+        Page::single(v)
+            .to_svg()
+            .unwrap()
+            .to_string()
+            .matches("<line")
+            .count()
+    };
+    assert!(count_lines(&minor) > count_lines(&plain));
+
+    let text = Page::single(&minor).dimensions(40, 20).to_text().unwrap();
+    assert!(text.contains('\''));
+    assert!(text.contains('·'));
+}
@@ -0,0 +1,63 @@
+use plotlib::page::Page;
+use plotlib::repr::Plot;
+use plotlib::style::{LineStyle, PointStyle};
+use plotlib::view::ContinuousView;
+
+fn view() -> ContinuousView {
+    let rate = Plot::new(vec![(0., 100.), (5., 300.), (10., 200.)])
+        .point_style(PointStyle::new())
+        .legend("rate".into());
+    let latency = Plot::new(vec![(0., 0.1), (5., 0.5), (10., 0.9)])
+        .line_style(LineStyle::new())
+        .legend("latency".into());
+    ContinuousView::new()
+        .add(rate)
+        .add_y2(latency)
+        .y_label("requests")
+        .y2_label("seconds")
+        .y2_range(0., 1.)
+}
+
+#[test]
+fn test_secondary_axis_svg() {
+    let svg = Page::single(&view()).to_svg().unwrap().to_string();
+    // Labels from both y-axes
+    assert!(svg.contains("\n300\n"));
+    assert!(svg.contains("\n0.8\n"));
+    assert!(svg.contains("\nseconds\n"));
+    assert!(svg.contains("rotate(90"));
+    // The legend says which axis each series is plotted against
+    assert!(svg.contains("\nrate (left)\n"));
+    assert!(svg.contains("\nlatency (right)\n"));
+}
+
+#[test]
+fn test_secondary_axis_text() {
+    let text = Page::single(&view()).dimensions(40, 12).to_text().unwrap();
+    assert!(text.contains("|- 0.8"));
+    assert!(text.contains("300-|"));
+    // The legend is listed beneath the axes, saying which axis each series is plotted against
+    let lines: Vec<&str> = text.lines().rev().take(2).collect();
+    assert!(lines[1].contains(" ●  rate (left)"), "{:?}", lines);
+    assert!(lines[0].contains("--- latency (right)"), "{:?}", lines);
+}
+
+#[test]
+fn test_no_secondary_axis_by_default() {
+    let p = Plot::new(vec![(0., 1.), (1., 2.)])
+        .point_style(PointStyle::new())
+        .legend("only".into());
+    let v = ContinuousView::new().add(p);
+    let svg = Page::single(&v).to_svg().unwrap().to_string();
+    assert!(!svg.contains("rotate(90"));
+    assert!(svg.contains("\nonly\n"));
+}
+
+#[test]
+fn test_secondary_axis_range_is_checked() {
+    let p1 = Plot::new(vec![(0., 1.), (1., 2.)]).point_style(PointStyle::new());
+    let p2 = Plot::new(vec![(0., -1.), (1., 2.)]).point_style(PointStyle::new());
+    let v = ContinuousView::new().add(p1).add_y2(p2).y2_log(10.);
+    let err = Page::single(&v).to_svg().unwrap_err();
+    assert!(err.to_string().contains("y2"));
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::elements;
+use plotlib::page::Page;
+use plotlib::repr::{CategoricalRepresentation, Violin, ViolinInner};
+use plotlib::style::BoxStyle;
+use plotlib::view::CategoricalView;
+
+fn svg(v: Violin) -> String {
+    let view = CategoricalView::new().add(v);
+    Page::single(&view).to_svg().unwrap().to_string()
+}
+
+/// How far the first polygon in some SVG reaches across the page
+fn polygon_width(svg: &str) -> f64 {
+    let polygon = elements(svg, "<polygon").next().unwrap();
+    let start = polygon.find(" points=\"").unwrap() + 9;
+    let points = &polygon[start..start + polygon[start..].find('"').unwrap()];
+    let xs: Vec<f64> = points
+        .split(' ')
+        .map(|point| point.split(',').next().unwrap().parse().unwrap())
+        .collect();
+    let (min, max) = xs.iter().fold((f64::MAX, f64::MIN), |(min, max), &x| {
+        (min.min(x), max.max(x))
+    });
+    max - min
+}
+
+#[test]
+fn test_violin_range() {
+    // The density reaches twice the bandwidth beyond the values
+    let v = Violin::from_slice(&[1., 2., 4.]).bandwidth(0.5);
+    assert_eq!(v.range(), (0., 5.));
+
+    let v = Violin::from_slice(&[1., 2., 4.])
+        .split_with(vec![3., 8.])
+        .bandwidth(0.5);
+    assert_eq!(v.range(), (0., 9.));
+}
+
+#[test]
+fn test_violin_svg() {
+    let v = Violin::from_slice(&[1., 1.2, 1.4, 3.8, 4., 4.1])
+        .label("a")
+        .style(&BoxStyle::new().fill("steelblue"));
+    assert_eq!(v.get_inner(), ViolinInner::Box);
+    let box_inner = svg(v.clone());
+    assert_eq!(box_inner.matches("<polygon").count(), 1);
+    assert!(box_inner.contains("fill=\"white\""));
+
+    let quartiles = svg(v.inner(ViolinInner::Quartiles));
+    assert_eq!(quartiles.matches("stroke-dasharray").count(), 3);
+    assert!(!quartiles.contains("fill=\"white\""));
+}
+
+#[test]
+fn test_split_violin() {
+    let v = Violin::from_vec(vec![0.8, 1., 1.1, 1.3])
+        .split_with(vec![3.5, 4., 4.2, 5.1])
+        .label("cache")
+        .legend("Hit")
+        .style(&BoxStyle::new().fill("steelblue"))
+        .split_legend("Miss")
+        .split_style(&BoxStyle::new().fill("wheat"))
+        .inner(ViolinInner::Quartiles);
+    assert_eq!(v.legend_entries().len(), 2);
+
+    let svg = svg(v);
+    // A polygon for each side, and a swatch in the legend for each
+    assert_eq!(svg.matches("<polygon").count(), 2);
+    assert_eq!(svg.matches("fill=\"wheat\"").count(), 2);
+    assert_eq!(svg.matches("stroke-dasharray").count(), 6);
+    assert!(svg.contains("\nMiss\n"));
+}
+
+#[test]
+fn test_split_style_before_split() {
+    let v = Violin::from_vec(vec![0.8, 1., 1.1, 1.3])
+        .split_legend("Miss")
+        .split_style(&BoxStyle::new().fill("wheat"));
+    // Nothing of the right-hand side is shown until it has values
+    assert!(v.legend_entries().is_empty());
+
+    let v = v.split_with(vec![3.5, 4., 4.2, 5.1]).label("cache");
+    assert_eq!(v.legend_entries().len(), 1);
+    let svg = svg(v);
+    assert_eq!(svg.matches("fill=\"wheat\"").count(), 2);
+    assert!(svg.contains("\nMiss\n"));
+}
+
+#[test]
+fn test_split_violin_with_an_empty_side() {
+    let data = vec![0.8, 1., 1.1, 1.3];
+    let full = polygon_width(&svg(Violin::from_vec(data.clone()).label("a")));
+
+    // The side with values is drawn as a half, in its own style, not mirrored into a whole violin
+    let left = svg(Violin::from_vec(data.clone()).split_with(vec![]).label("a"));
+    assert_eq!(left.matches("<polygon").count(), 1);
+    assert!((polygon_width(&left) - full / 2.).abs() < 1e-6);
+
+    let right = svg(Violin::from_vec(vec![])
+        .split_with(data)
+        .split_style(&BoxStyle::new().fill("wheat"))
+        .label("a"));
+    assert_eq!(right.matches("<polygon").count(), 1);
+    assert!(right.contains("fill=\"wheat\""));
+    assert!((polygon_width(&right) - full / 2.).abs() < 1e-6);
+}
+
+#[test]
+fn test_violin_rejects_invalid_bandwidth() {
+    for bandwidth in [0., -1., f64::NAN, f64::INFINITY] {
+        let view =
+            CategoricalView::new().add(Violin::from_slice(&[1., 2., 4.]).bandwidth(bandwidth));
+        let err = Page::single(&view).to_svg().unwrap_err();
+        assert!(err.to_string().contains("bandwidth"), "{}", err);
+    }
+}